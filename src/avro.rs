@@ -0,0 +1,374 @@
+//! Avro data source.
+//!
+//! Avro types are mapped to Arrow types as follows:
+//! * `null`, `boolean`, `int`, `long`, `float`, `double` and `string` map to the equivalent Arrow types
+//! * `enum` maps to `Utf8`, holding the symbol
+//! * `record` maps to `Struct`, and `array` maps to `List`
+//! * a `union` of `null` and a single other type maps to a nullable column of that type.
+//!   Any other `union` is not supported
+//! * the logical types `date`, `time-millis`, `time-micros`, `timestamp-millis` and `timestamp-micros`
+//!   are read as their underlying `int` or `long`
+//! * `bytes`, `fixed`, `map` and the remaining logical types are not supported
+use std::any::Any;
+use std::fs::File;
+use std::io::BufReader;
+use std::sync::Arc;
+
+use avro_rs::{schema::Schema as AvroSchema, types::Value, Reader as AvroReader};
+
+use arrow::array::{
+    make_array, ArrayData, ArrayRef, BooleanArray, Float32Array, Float64Array, Int32Array,
+    Int64Array, NullArray, StringArray,
+};
+use arrow::buffer::Buffer;
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef, ToByteSlice};
+use arrow::error::Result as ArrowResult;
+use arrow::record_batch::RecordBatch;
+use arrow::util::bit_util;
+
+use datafusion::datasource::datasource::{Statistics, TableProvider};
+use datafusion::error::{DataFusionError, Result};
+use datafusion::logical_plan::Expr;
+use datafusion::physical_plan::ExecutionPlan;
+
+use crate::execution::{project_schema, spawn_batches, ScanExec};
+
+fn avro_error(e: avro_rs::Error) -> DataFusionError {
+    DataFusionError::Execution(format!("Avro error: {}", e))
+}
+
+fn not_supported(schema: &AvroSchema) -> DataFusionError {
+    DataFusionError::NotImplemented(format!("The Avro type {:?} is not supported", schema))
+}
+
+/// converts an Avro schema into an Arrow DataType and whether it is nullable
+fn to_data_type(schema: &AvroSchema) -> Result<(DataType, bool)> {
+    Ok(match schema {
+        AvroSchema::Null => (DataType::Null, true),
+        AvroSchema::Boolean => (DataType::Boolean, false),
+        AvroSchema::Int | AvroSchema::Date | AvroSchema::TimeMillis => (DataType::Int32, false),
+        AvroSchema::Long
+        | AvroSchema::TimeMicros
+        | AvroSchema::TimestampMillis
+        | AvroSchema::TimestampMicros => (DataType::Int64, false),
+        AvroSchema::Float => (DataType::Float32, false),
+        AvroSchema::Double => (DataType::Float64, false),
+        AvroSchema::String | AvroSchema::Enum { .. } => (DataType::Utf8, false),
+        AvroSchema::Array(item) => {
+            let (data_type, nullable) = to_data_type(item)?;
+            (
+                DataType::List(Box::new(Field::new("item", data_type, nullable))),
+                false,
+            )
+        }
+        AvroSchema::Record { fields, .. } => (
+            DataType::Struct(
+                fields
+                    .iter()
+                    .map(|field| {
+                        let (data_type, nullable) = to_data_type(&field.schema)?;
+                        Ok(Field::new(&field.name, data_type, nullable))
+                    })
+                    .collect::<Result<_>>()?,
+            ),
+            false,
+        ),
+        AvroSchema::Union(union) => {
+            let variants = union
+                .variants()
+                .iter()
+                .filter(|variant| **variant != AvroSchema::Null)
+                .collect::<Vec<_>>();
+            match variants.as_slice() {
+                [variant] if union.is_nullable() => (to_data_type(variant)?.0, true),
+                _ => return Err(not_supported(schema)),
+            }
+        }
+        other => return Err(not_supported(other)),
+    })
+}
+
+// the value of the fields missing from a record
+static NULL: Value = Value::Null;
+
+/// the value inside `value` if it is a union, or `value` otherwise
+fn unwrap_union(value: &Value) -> &Value {
+    match value {
+        Value::Union(value) => unwrap_union(value),
+        value => value,
+    }
+}
+
+/// the value of the field `name` of the record `value`, or null if there is none
+fn field_value<'a>(value: &'a Value, name: &str) -> &'a Value {
+    match value {
+        Value::Record(fields) => fields
+            .iter()
+            .find(|(field, _)| field == name)
+            .map(|(_, value)| unwrap_union(value))
+            .unwrap_or(&NULL),
+        _ => &NULL,
+    }
+}
+
+fn unexpected(value: &Value, data_type: &DataType) -> DataFusionError {
+    DataFusionError::Execution(format!(
+        "The Avro value {:?} can't be read as {:?}",
+        value, data_type
+    ))
+}
+
+/// converts each of `values` with `f`, or errors if `f` returns `None` on a value other than null
+fn collect<'a, T>(
+    values: &[&'a Value],
+    data_type: &DataType,
+    f: impl Fn(&'a Value) -> Option<T>,
+) -> Result<Vec<Option<T>>> {
+    values
+        .iter()
+        .map(|value| match value {
+            Value::Null => Ok(None),
+            value => f(value)
+                .map(Some)
+                .ok_or_else(|| unexpected(value, data_type)),
+        })
+        .collect()
+}
+
+/// the validity bitmap of an array whose slots are valid where `valid` is true, or `None` if all are
+fn null_bit_buffer(valid: &[bool]) -> Option<Buffer> {
+    if valid.iter().all(|v| *v) {
+        return None;
+    }
+    let mut bits = vec![0u8; (valid.len() + 7) / 8];
+    for (i, v) in valid.iter().enumerate() {
+        if *v {
+            bit_util::set_bit(&mut bits, i);
+        }
+    }
+    Some(Buffer::from(bits))
+}
+
+/// converts Avro values into an Arrow array of type `data_type`, as returned by `to_data_type`
+fn to_array(values: &[&Value], data_type: &DataType) -> Result<ArrayRef> {
+    Ok(match data_type {
+        DataType::Null => Arc::new(NullArray::new(values.len())),
+        DataType::Boolean => Arc::new(BooleanArray::from(collect(
+            values,
+            data_type,
+            |v| match v {
+                Value::Boolean(v) => Some(*v),
+                _ => None,
+            },
+        )?)),
+        DataType::Int32 => Arc::new(Int32Array::from(collect(values, data_type, |v| match v {
+            Value::Int(v) | Value::Date(v) | Value::TimeMillis(v) => Some(*v),
+            _ => None,
+        })?)),
+        DataType::Int64 => Arc::new(Int64Array::from(collect(values, data_type, |v| match v {
+            Value::Long(v)
+            | Value::TimeMicros(v)
+            | Value::TimestampMillis(v)
+            | Value::TimestampMicros(v) => Some(*v),
+            _ => None,
+        })?)),
+        DataType::Float32 => Arc::new(Float32Array::from(collect(
+            values,
+            data_type,
+            |v| match v {
+                Value::Float(v) => Some(*v),
+                _ => None,
+            },
+        )?)),
+        DataType::Float64 => Arc::new(Float64Array::from(collect(
+            values,
+            data_type,
+            |v| match v {
+                Value::Double(v) => Some(*v),
+                _ => None,
+            },
+        )?)),
+        DataType::Utf8 => Arc::new(StringArray::from(collect(
+            values,
+            data_type,
+            |v| match v {
+                Value::String(v) | Value::Enum(_, v) => Some(v.as_str()),
+                _ => None,
+            },
+        )?)),
+        DataType::List(field) => {
+            // the items of all lists, and where each list starts and ends in them
+            let mut offsets = vec![0i32];
+            let mut items = vec![];
+            let mut valid = vec![];
+            for value in values {
+                match value {
+                    Value::Null => valid.push(false),
+                    Value::Array(values) => {
+                        valid.push(true);
+                        items.extend(values.iter().map(unwrap_union));
+                    }
+                    other => return Err(unexpected(other, data_type)),
+                }
+                offsets.push(items.len() as i32);
+            }
+            let items = to_array(&items, field.data_type())?;
+
+            let builder = ArrayData::builder(data_type.clone())
+                .len(values.len())
+                .add_buffer(Buffer::from(offsets.to_byte_slice()))
+                .add_child_data(items.data());
+            let builder = match null_bit_buffer(&valid) {
+                Some(buffer) => builder.null_bit_buffer(buffer),
+                None => builder,
+            };
+            make_array(builder.build())
+        }
+        DataType::Struct(fields) => {
+            let valid = values
+                .iter()
+                .map(|value| match value {
+                    Value::Null => Ok(false),
+                    Value::Record(_) => Ok(true),
+                    other => Err(unexpected(other, data_type)),
+                })
+                .collect::<Result<Vec<_>>>()?;
+            let children = fields
+                .iter()
+                .map(|field| {
+                    let values = values
+                        .iter()
+                        .map(|value| field_value(value, field.name()))
+                        .collect::<Vec<_>>();
+                    Ok(to_array(&values, field.data_type())?.data())
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            let builder = ArrayData::builder(data_type.clone())
+                .len(values.len())
+                .child_data(children);
+            let builder = match null_bit_buffer(&valid) {
+                Some(buffer) => builder.null_bit_buffer(buffer),
+                None => builder,
+            };
+            make_array(builder.build())
+        }
+        other => {
+            return Err(DataFusionError::NotImplemented(format!(
+                "The Arrow type {:?} can't be read from Avro",
+                other
+            )))
+        }
+    })
+}
+
+/// A table backed by an Avro file. The file is read when the table's scan is executed.
+pub(crate) struct AvroTable {
+    path: String,
+    schema: SchemaRef,
+}
+
+impl AvroTable {
+    /// Creates a new table from the file at `path`, whose schema is converted from the file's Avro schema.
+    pub fn try_new(path: &str) -> Result<Self> {
+        let reader = AvroReader::new(BufReader::new(File::open(path)?)).map_err(avro_error)?;
+
+        let schema = match reader.writer_schema() {
+            AvroSchema::Record { fields, .. } => fields
+                .iter()
+                .map(|field| {
+                    let (data_type, nullable) = to_data_type(&field.schema)?;
+                    Ok(Field::new(&field.name, data_type, nullable))
+                })
+                .collect::<Result<_>>()?,
+            other => {
+                return Err(DataFusionError::Plan(format!(
+                    "The schema of an Avro file must be a record, but it is {:?}",
+                    other
+                )))
+            }
+        };
+
+        Ok(Self {
+            path: path.to_string(),
+            schema: Arc::new(Schema::new(schema)),
+        })
+    }
+}
+
+/// reads the file at `path` into batches of `batch_size` rows with the columns of `schema`, and sends each batch to `send`
+fn read(
+    path: &str,
+    schema: SchemaRef,
+    batch_size: usize,
+    send: &mut dyn FnMut(ArrowResult<RecordBatch>) -> bool,
+) -> Result<()> {
+    // converts records into a batch with the columns of `schema`
+    let to_batch = |records: &[Value]| -> Result<RecordBatch> {
+        let columns = schema
+            .fields()
+            .iter()
+            .map(|field| {
+                let values = records
+                    .iter()
+                    .map(|record| field_value(record, field.name()))
+                    .collect::<Vec<_>>();
+                to_array(&values, field.data_type())
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(RecordBatch::try_new(schema.clone(), columns)?)
+    };
+
+    let reader = AvroReader::new(BufReader::new(File::open(path)?)).map_err(avro_error)?;
+    let mut records = Vec::with_capacity(batch_size);
+    for record in reader {
+        records.push(record.map_err(avro_error)?);
+        if records.len() == batch_size {
+            if !send(Ok(to_batch(&records)?)) {
+                return Ok(());
+            }
+            records.clear();
+        }
+    }
+    if !records.is_empty() {
+        send(Ok(to_batch(&records)?));
+    }
+    Ok(())
+}
+
+impl TableProvider for AvroTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn scan(
+        &self,
+        projection: &Option<Vec<usize>>,
+        batch_size: usize,
+        _filters: &[Expr],
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let (_, projected_schema) = project_schema(&self.schema, projection);
+
+        let path = self.path.clone();
+        Ok(Arc::new(ScanExec::new(
+            "AvroExec",
+            projected_schema.clone(),
+            1,
+            move |_| {
+                let path = path.clone();
+                let schema = projected_schema.clone();
+                Ok(spawn_batches(schema.clone(), move |send| {
+                    read(&path, schema, batch_size, send)
+                }))
+            },
+        )))
+    }
+
+    fn statistics(&self) -> Statistics {
+        Statistics::default()
+    }
+}
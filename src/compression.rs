@@ -0,0 +1,67 @@
+use std::io::Read;
+
+use bzip2::read::BzDecoder;
+use flate2::read::MultiGzDecoder;
+use xz2::read::XzDecoder;
+
+use datafusion::error::{DataFusionError, Result};
+
+/// The compression of a CSV or newline-delimited JSON file
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Compression {
+    Uncompressed,
+    Gzip,
+    Bzip2,
+    Zstd,
+    Xz,
+}
+
+impl Compression {
+    /// the compression of the file at `path`, according to its extension
+    pub fn from_extension(path: &str) -> Self {
+        if path.ends_with(".gz") {
+            Compression::Gzip
+        } else if path.ends_with(".bz2") {
+            Compression::Bzip2
+        } else if path.ends_with(".zst") || path.ends_with(".zstd") {
+            Compression::Zstd
+        } else if path.ends_with(".xz") {
+            Compression::Xz
+        } else {
+            Compression::Uncompressed
+        }
+    }
+
+    /// the compression of the file at `path`: `compression` when given, or according to its extension
+    pub fn of(compression: Option<Compression>, path: &str) -> Self {
+        compression.unwrap_or_else(|| Compression::from_extension(path))
+    }
+
+    /// the compression named `name`, one of "gzip", "bz2", "zstd", "xz" or "none"
+    pub fn try_from_name(name: &str) -> Result<Self> {
+        Ok(match name {
+            "gzip" => Compression::Gzip,
+            "bz2" => Compression::Bzip2,
+            "zstd" => Compression::Zstd,
+            "xz" => Compression::Xz,
+            "none" => Compression::Uncompressed,
+            other => {
+                return Err(DataFusionError::Plan(format!(
+                    "The compression '{}' is not supported",
+                    other
+                )))
+            }
+        })
+    }
+
+    /// wraps `reader` so that it is decompressed while it is read
+    pub fn decompress(&self, reader: Box<dyn Read>) -> Result<Box<dyn Read>> {
+        Ok(match self {
+            Compression::Uncompressed => reader,
+            Compression::Gzip => Box::new(MultiGzDecoder::new(reader)),
+            Compression::Bzip2 => Box::new(BzDecoder::new(reader)),
+            Compression::Zstd => Box::new(zstd::stream::read::Decoder::new(reader)?),
+            Compression::Xz => Box::new(XzDecoder::new(reader)),
+        })
+    }
+}
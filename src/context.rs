@@ -1,4 +1,6 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
 
 use rand::distributions::Alphanumeric;
 use rand::Rng;
@@ -6,14 +8,32 @@ use rand::Rng;
 use pyo3::prelude::*;
 
 use arrow::record_batch::RecordBatch;
-use datafusion::datasource::MemTable;
-use datafusion::execution::context::ExecutionContext as _ExecutionContext;
+use datafusion::datasource::{MemTable, TableProvider};
+use datafusion::execution::context::{
+    ExecutionConfig, ExecutionContext as _ExecutionContext, ExecutionContextState,
+};
+use datafusion::logical_plan::LogicalPlan;
+use datafusion::physical_plan::csv::CsvReadOptions;
 
+use crate::avro::AvroTable;
+use crate::compression::Compression;
 use crate::dataframe;
+use crate::dataset::DatasetTable;
 use crate::errors;
 use crate::functions;
+use crate::information_schema;
+use crate::ipc::IpcTable;
+use crate::json::JsonTable;
+use crate::listing::ListingTable;
+use crate::multi_file::{FileFormat, MultiFileTable};
+use crate::object_store::{self, LocalStore, ObjectStore};
+use crate::provider::PyTableProvider;
+use crate::sql;
+use crate::sqlite::{self, SqliteTable};
+use crate::to_py;
 use crate::to_rust;
 use crate::types::PyDataType;
+use crate::view::ViewTable;
 
 /// `ExecutionContext` is able to plan and execute DataFusion plans.
 /// It has a powerful optimizer, a physical planner for local execution, and a
@@ -21,23 +41,171 @@ use crate::types::PyDataType;
 #[pyclass(unsendable)]
 pub(crate) struct ExecutionContext {
     ctx: _ExecutionContext,
+    // the schemas created via `create_schema`. Tables of a schema are registered under
+    // the qualified name `<schema>.<table>`, which is how SQL statements refer to them.
+    schemas: HashSet<String>,
+    // the object stores registered via `register_object_store`, by URL scheme
+    object_stores: HashMap<String, Arc<dyn ObjectStore>>,
+}
+
+impl ExecutionContext {
+    /// errors if `name` is qualified by the reserved `information_schema` schema. Names qualified by
+    /// a schema that was not created via `create_schema` are valid table names, as they were before
+    /// schemas existed: e.g. "a.b" is the table "a.b" until the schema "a" is created.
+    fn check_name(&self, name: &str) -> PyResult<()> {
+        match name.rsplitn(2, '.').nth(1) {
+            Some(schema) if schema == information_schema::SCHEMA => Err(
+                errors::DataFusionError::Common(format!("The schema '{}' is reserved", schema))
+                    .into(),
+            ),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns a DataFrame reading the table that `register` registers under the name it is given.
+    fn anonymous_dataframe<F>(&mut self, register: F) -> PyResult<dataframe::DataFrame>
+    where
+        F: FnOnce(&mut Self, &str) -> PyResult<()>,
+    {
+        let state = self.ctx.state.clone();
+        let plan = anonymous_plan(&state, |name| register(self, name))?;
+        Ok(dataframe::DataFrame::new(state, plan))
+    }
+
+    /// When `path` is a URL such as `s3://bucket/data` (or a list of URLs of the same scheme), returns
+    /// the object store registered for its scheme and the paths of the objects at the URLs, without the scheme.
+    /// Returns `None` for local paths, and errors when `path` is a list of both URLs and local paths.
+    /// When `extension` is given, only the objects with it (see `has_extension`) are listed under a directory.
+    fn store_paths(
+        &self,
+        path: &PyAny,
+        extension: Option<&str>,
+    ) -> PyResult<Option<(Arc<dyn ObjectStore>, Vec<String>)>> {
+        let urls = match path.extract::<String>() {
+            Ok(path) => vec![path],
+            Err(_) => match path.extract::<Vec<String>>() {
+                Ok(paths) => paths,
+                Err(_) => return Ok(None),
+            },
+        };
+        let schemes = urls
+            .iter()
+            .map(|url| url.find("://").map(|i| &url[..i]))
+            .collect::<HashSet<_>>();
+        let scheme = match schemes.into_iter().collect::<Vec<_>>().as_slice() {
+            [None] | [] => return Ok(None),
+            [Some(scheme)] => scheme.to_string(),
+            _ => {
+                return Err(errors::DataFusionError::Common(
+                    "The paths must either all be local or all be URLs of the same scheme"
+                        .to_string(),
+                )
+                .into())
+            }
+        };
+        let store = match self.object_stores.get(&scheme) {
+            Some(store) => store.clone(),
+            None => {
+                return Err(errors::DataFusionError::Common(format!(
+                    "No object store is registered for the scheme '{}'",
+                    scheme
+                ))
+                .into())
+            }
+        };
+
+        let mut paths = vec![];
+        for url in &urls {
+            let key = &url[scheme.len() + 3..];
+            let mut listed = errors::wrap(store.list(key))?;
+            listed.retain(|path| path == key || extension.map_or(true, |e| has_extension(path, e)));
+            if listed.is_empty() {
+                return Err(
+                    errors::DataFusionError::Common(format!("No files at '{}'", url)).into(),
+                );
+            }
+            paths.extend(listed);
+        }
+        Ok(Some((store, paths)))
+    }
+
+    /// registers `provider` as a table named `name`
+    fn register_provider(
+        &mut self,
+        name: &str,
+        provider: Box<dyn TableProvider + Send + Sync>,
+    ) -> PyResult<()> {
+        self.check_name(name)?;
+        self.ctx.register_table(name, provider);
+        Ok(())
+    }
 }
 
 #[pymethods]
 impl ExecutionContext {
+    /// Creates a new context.
+    /// `batch_size` is the number of rows of each batch produced by the operators and
+    /// `target_partitions` is the number of partitions (and threads) used to execute plans.
     #[new]
-    fn new() -> Self {
-        ExecutionContext {
-            ctx: _ExecutionContext::new(),
+    #[args(batch_size = "None", target_partitions = "None")]
+    fn new(batch_size: Option<usize>, target_partitions: Option<usize>) -> PyResult<Self> {
+        let mut config = ExecutionConfig::new();
+        if let Some(batch_size) = batch_size {
+            config = config.with_batch_size(batch_size);
+        }
+        if let Some(target_partitions) = target_partitions {
+            config = config.with_concurrency(target_partitions);
         }
+
+        let ctx = _ExecutionContext::with_config(config);
+        information_schema::register(&ctx.state);
+
+        Ok(ExecutionContext {
+            ctx,
+            schemas: HashSet::new(),
+            object_stores: HashMap::new(),
+        })
     }
 
     /// Returns a DataFrame whose plan corresponds to the SQL statement.
+    /// Besides queries, `SHOW TABLES`, `SHOW COLUMNS FROM <table>` and `DESCRIBE <table>` are supported,
+    /// as well as the `information_schema.tables` and `information_schema.columns` views.
+    /// Full and cross joins are planned as in `DataFrame.join` (e.g. the columns of both sides that
+    /// have the same name, other than the keys of `USING`, are suffixed with "_l" and "_r").
+    /// Semi and anti joins, which have no SQL syntax, are only supported by `DataFrame.join`.
     fn sql(&mut self, query: &str) -> PyResult<dataframe::DataFrame> {
-        let df = self
-            .ctx
-            .sql(query)
-            .map_err(|e| -> errors::DataFusionError { e.into() })?;
+        let rewritten = information_schema::rewrite(query);
+        if let Some((_, Some(table))) = &rewritten {
+            let exists = self
+                .ctx
+                .state
+                .lock()
+                .unwrap()
+                .datasources
+                .keys()
+                .any(|name| information_schema::is_same_table(name, table));
+            if !exists {
+                return Err(
+                    errors::DataFusionError::Common(format!("No table named '{}'", table)).into(),
+                );
+            }
+        }
+        let query = rewritten
+            .as_ref()
+            .map_or(query, |(rewritten, _)| rewritten.as_str());
+
+        let plan = sql::plan(&self.ctx.state, query)?;
+        Ok(dataframe::DataFrame::new(self.ctx.state.clone(), plan))
+    }
+
+    /// Returns a DataFrame that reads the registered table `name`.
+    fn table(&mut self, name: &str) -> PyResult<dataframe::DataFrame> {
+        if !self.ctx.tables().contains(name) {
+            return Err(
+                errors::DataFusionError::Common(format!("No table named '{}'", name)).into(),
+            );
+        }
+        let df = errors::wrap(self.ctx.table(name))?;
         Ok(dataframe::DataFrame::new(
             self.ctx.state.clone(),
             df.to_logical_plan(),
@@ -49,33 +217,350 @@ impl ExecutionContext {
         partitions: Vec<Vec<PyObject>>,
         py: Python,
     ) -> PyResult<dataframe::DataFrame> {
-        let partitions: Vec<Vec<RecordBatch>> = partitions
-            .iter()
-            .map(|batches| {
-                batches
-                    .iter()
-                    .map(|batch| to_rust::to_rust_batch(batch.as_ref(py)))
-                    .collect()
-            })
-            .collect::<PyResult<_>>()?;
+        self.anonymous_dataframe(|ctx, name| ctx.register_record_batches(name, partitions, py))
+    }
 
-        let table = errors::wrap(MemTable::try_new(partitions[0][0].schema(), partitions))?;
+    /// Registers `partitions` of pyarrow `RecordBatch`es as an in-memory table named `name`.
+    fn register_record_batches(
+        &mut self,
+        name: &str,
+        partitions: Vec<Vec<PyObject>>,
+        py: Python,
+    ) -> PyResult<()> {
+        let table = mem_table(partitions, py)?;
 
-        // generate a random (unique) name for this table
-        let name = rand::thread_rng()
-            .sample_iter(&Alphanumeric)
-            .take(10)
-            .collect::<String>();
+        self.register_provider(name, Box::new(table))
+    }
 
-        self.ctx.register_table(&name, Box::new(table));
-        Ok(dataframe::DataFrame::new(
-            self.ctx.state.clone(),
-            errors::wrap(self.ctx.table(&name))?.to_logical_plan(),
-        ))
+    /// Registers the DataFrame `df` as a view named `name`.
+    /// The DataFrame's plan is only executed when a query that uses the view is executed.
+    fn register_view(&mut self, name: &str, df: &dataframe::DataFrame) -> PyResult<()> {
+        let config = self.ctx.state.lock().unwrap().config.clone();
+        let table = ViewTable::new(df.plan().clone(), config);
+
+        self.register_provider(name, Box::new(table))
+    }
+
+    /// Registers the Python object `provider` as a table named `name`.
+    /// `provider` must implement `schema()` and `scan(projection, filters, limit)`;
+    /// see the README for the details of this interface.
+    fn register_table(&mut self, name: &str, provider: PyObject, py: Python) -> PyResult<()> {
+        let table = PyTableProvider::try_new(provider, py)?;
+
+        self.register_provider(name, Box::new(table))
+    }
+
+    /// Registers the `pyarrow.dataset.Dataset` `dataset` as a table named `name`.
+    fn register_dataset(&mut self, name: &str, dataset: PyObject, py: Python) -> PyResult<()> {
+        let table = DatasetTable::try_new(dataset, py)?;
+
+        self.register_provider(name, Box::new(table))
+    }
+
+    /// Registers `store` as the object store of the URLs with the scheme `scheme` (e.g. "s3"),
+    /// from which `register_parquet`, `register_csv` and `register_json` read. `store` is an
+    /// `InMemoryObjectStore`, a `LocalFileSystem` or any `pyarrow.fs.FileSystem`, e.g. a
+    /// `pyarrow.fs.S3FileSystem` (whose `endpoint_override` supports S3-compatible services such as MinIO).
+    fn register_object_store(&mut self, scheme: &str, store: &PyAny) -> PyResult<()> {
+        let store = object_store::to_rust_store(store)?;
+        self.object_stores.insert(scheme.to_string(), store);
+        Ok(())
+    }
+
+    /// Registers the Parquet file (or directory of Parquet files) at `path` as a table named `name`.
+    /// `path` can also be a glob pattern or a list of paths (of files or directories), whose files are
+    /// merged into a single table, or a URL such as `s3://bucket/data` (or a list of URLs), whose objects
+    /// are read from the object store registered for its scheme via `register_object_store`.
+    /// Only the files of directories with the extension ".parquet" are read.
+    fn register_parquet(&mut self, name: &str, path: &PyAny) -> PyResult<()> {
+        if let Some((store, paths)) = self.store_paths(path, None)? {
+            let table = errors::wrap(MultiFileTable::try_new(
+                Some(store),
+                paths,
+                FileFormat::Parquet,
+                None,
+            ))?;
+            return self.register_provider(name, Box::new(table));
+        }
+
+        match expand_directories(expand_paths(path)?, Some(".parquet"))?.as_slice() {
+            [path] => {
+                self.check_name(name)?;
+                errors::wrap(self.ctx.register_parquet(name, path))?;
+                Ok(())
+            }
+            paths => {
+                let table = errors::wrap(MultiFileTable::try_new(
+                    None,
+                    paths.to_vec(),
+                    FileFormat::Parquet,
+                    None,
+                ))?;
+                self.register_provider(name, Box::new(table))
+            }
+        }
+    }
+
+    /// Registers the directory of Parquet files at `path`, partitioned in the Hive style
+    /// (e.g. `year=2026/month=10/part-0.parquet`), as a table named `name`.
+    /// The partition keys are exposed as columns, typed according to `partitioning`, a list of
+    /// `(name, pyarrow.DataType)`. When `partitioning` is `None`, the keys are read from the directories
+    /// and are typed `int64` when all their values are integers and `utf8` otherwise.
+    /// The values of the partitions named `__HIVE_DEFAULT_PARTITION__` are null, and the schemas of the files
+    /// are merged as in `register_parquet`.
+    /// Files whose partition values do not satisfy a query's filters are not read.
+    #[args(partitioning = "None", file_extension = "\".parquet\"")]
+    fn register_listing_table(
+        &mut self,
+        name: &str,
+        path: &str,
+        partitioning: Option<Vec<(String, PyDataType)>>,
+        file_extension: &str,
+    ) -> PyResult<()> {
+        let partitioning = partitioning.map(|partitioning| {
+            partitioning
+                .into_iter()
+                .map(|(key, data_type)| (key, data_type.data_type))
+                .collect()
+        });
+        let table = errors::wrap(ListingTable::try_new(path, partitioning, file_extension))?;
+
+        self.register_provider(name, Box::new(table))
+    }
+
+    /// Registers the CSV file (or directory of CSV files) at `path` as a table named `name`.
+    /// `path` can also be a glob pattern or a list of paths, whose files are merged into a single table,
+    /// or a URL whose scheme has an object store, as in `register_parquet`.
+    /// Unless `schema` is passed, the schema is inferred from the first
+    /// `schema_infer_max_records` records (of each file).
+    /// Files compressed with `compression` ("gzip", "bz2", "zstd", "xz" or "none") are decompressed
+    /// while read. When `None`, the compression of each file is detected from its extension.
+    /// Only the files of directories with `file_extension`, possibly followed by the extension
+    /// of their compression (e.g. "a.csv.gz"), are read.
+    #[args(
+        schema = "None",
+        has_header = "true",
+        delimiter = "\",\"",
+        schema_infer_max_records = "1000",
+        file_extension = "\".csv\"",
+        compression = "None"
+    )]
+    fn register_csv(
+        &mut self,
+        name: &str,
+        path: &PyAny,
+        schema: Option<&PyAny>,
+        has_header: bool,
+        delimiter: &str,
+        schema_infer_max_records: usize,
+        file_extension: &str,
+        compression: Option<&str>,
+    ) -> PyResult<()> {
+        let schema = match schema {
+            Some(schema) => Some(to_rust::to_rust_schema(schema)?),
+            None => None,
+        };
+        let delimiter = delimiter.as_bytes();
+        if delimiter.len() != 1 {
+            return Err(errors::DataFusionError::Common(
+                "Delimiter must be a single character".to_string(),
+            )
+            .into());
+        }
+
+        let compression = errors::wrap(compression.map(Compression::try_from_name).transpose())?;
+        if let Some((store, paths)) = self.store_paths(path, Some(file_extension))? {
+            let format = FileFormat::Csv {
+                has_header,
+                delimiter: delimiter[0],
+                schema_infer_max_records,
+                compression,
+            };
+            let table = errors::wrap(MultiFileTable::try_new(Some(store), paths, format, schema))?;
+            return self.register_provider(name, Box::new(table));
+        }
+
+        let paths = expand_directories(expand_paths(path)?, Some(file_extension))?;
+        let compressed = is_compressed(&paths, compression);
+
+        match paths.as_slice() {
+            [path] if !compressed => {
+                let mut options = CsvReadOptions::new()
+                    .has_header(has_header)
+                    .delimiter(delimiter[0])
+                    .schema_infer_max_records(schema_infer_max_records)
+                    .file_extension(file_extension);
+                if let Some(schema) = &schema {
+                    options = options.schema(schema);
+                }
+
+                self.check_name(name)?;
+                errors::wrap(self.ctx.register_csv(name, path, options))?;
+                Ok(())
+            }
+            paths => {
+                let format = FileFormat::Csv {
+                    has_header,
+                    delimiter: delimiter[0],
+                    schema_infer_max_records,
+                    compression,
+                };
+                let table = errors::wrap(MultiFileTable::try_new(
+                    None,
+                    paths.to_vec(),
+                    format,
+                    schema,
+                ))?;
+                self.register_provider(name, Box::new(table))
+            }
+        }
+    }
+
+    /// Registers the newline-delimited JSON file at `path` as a table named `name`.
+    /// `path` can also be a glob pattern or a list of paths, whose files are merged into a single table,
+    /// or a URL whose scheme has an object store, as in `register_parquet`.
+    /// Unless `schema` is passed, the schema is inferred from the first
+    /// `schema_infer_max_records` lines (of each file).
+    /// Compressed files are decompressed while read, as in `register_csv`.
+    #[args(
+        schema = "None",
+        schema_infer_max_records = "1000",
+        compression = "None"
+    )]
+    fn register_json(
+        &mut self,
+        name: &str,
+        path: &PyAny,
+        schema: Option<&PyAny>,
+        schema_infer_max_records: usize,
+        compression: Option<&str>,
+    ) -> PyResult<()> {
+        let schema = match schema {
+            Some(schema) => Some(to_rust::to_rust_schema(schema)?),
+            None => None,
+        };
+        let compression = errors::wrap(compression.map(Compression::try_from_name).transpose())?;
+        if let Some((store, paths)) = self.store_paths(path, None)? {
+            let format = FileFormat::Json {
+                schema_infer_max_records,
+                compression,
+            };
+            let table = errors::wrap(MultiFileTable::try_new(Some(store), paths, format, schema))?;
+            return self.register_provider(name, Box::new(table));
+        }
+
+        let paths = expand_directories(expand_paths(path)?, None)?;
+        let compressed = is_compressed(&paths, compression);
+
+        match paths.as_slice() {
+            [path] if !compressed => {
+                let table =
+                    errors::wrap(JsonTable::try_new(path, schema, schema_infer_max_records))?;
+                self.register_provider(name, Box::new(table))
+            }
+            paths => {
+                let format = FileFormat::Json {
+                    schema_infer_max_records,
+                    compression,
+                };
+                let table = errors::wrap(MultiFileTable::try_new(
+                    None,
+                    paths.to_vec(),
+                    format,
+                    schema,
+                ))?;
+                self.register_provider(name, Box::new(table))
+            }
+        }
+    }
+
+    /// Returns a DataFrame that reads the Parquet file (or directory of Parquet files) at `path`.
+    fn read_parquet(&mut self, path: &PyAny) -> PyResult<dataframe::DataFrame> {
+        self.anonymous_dataframe(|ctx, name| ctx.register_parquet(name, path))
+    }
+
+    /// Returns a DataFrame that reads the CSV file (or directory of CSV files) at `path`.
+    /// The options are the same as the ones of `register_csv`.
+    #[args(
+        schema = "None",
+        has_header = "true",
+        delimiter = "\",\"",
+        schema_infer_max_records = "1000",
+        file_extension = "\".csv\"",
+        compression = "None"
+    )]
+    fn read_csv(
+        &mut self,
+        path: &PyAny,
+        schema: Option<&PyAny>,
+        has_header: bool,
+        delimiter: &str,
+        schema_infer_max_records: usize,
+        file_extension: &str,
+        compression: Option<&str>,
+    ) -> PyResult<dataframe::DataFrame> {
+        self.anonymous_dataframe(|ctx, name| {
+            ctx.register_csv(
+                name,
+                path,
+                schema,
+                has_header,
+                delimiter,
+                schema_infer_max_records,
+                file_extension,
+                compression,
+            )
+        })
+    }
+
+    /// Returns a DataFrame that reads the newline-delimited JSON file at `path`.
+    /// The options are the same as the ones of `register_json`.
+    #[args(
+        schema = "None",
+        schema_infer_max_records = "1000",
+        compression = "None"
+    )]
+    fn read_json(
+        &mut self,
+        path: &PyAny,
+        schema: Option<&PyAny>,
+        schema_infer_max_records: usize,
+        compression: Option<&str>,
+    ) -> PyResult<dataframe::DataFrame> {
+        self.anonymous_dataframe(|ctx, name| {
+            ctx.register_json(name, path, schema, schema_infer_max_records, compression)
+        })
+    }
+
+    /// Registers the Arrow IPC file at `path`, in either the file (Feather v2) or the stream format,
+    /// as a table named `name`. The file is read batch by batch by the queries that use it;
+    /// with this version of Arrow, it is not memory-mapped and all of its columns are decoded.
+    fn register_ipc(&mut self, name: &str, path: &str) -> PyResult<()> {
+        let table = errors::wrap(IpcTable::try_new(path))?;
+
+        self.register_provider(name, Box::new(table))
     }
 
-    fn register_parquet(&mut self, name: &str, path: &str) -> PyResult<()> {
-        errors::wrap(self.ctx.register_parquet(name, path))?;
+    /// Registers the Avro file at `path` as a table named `name`.
+    /// See the `avro` module for how Avro types are mapped to Arrow types.
+    fn register_avro(&mut self, name: &str, path: &str) -> PyResult<()> {
+        let table = errors::wrap(AvroTable::try_new(path))?;
+
+        self.register_provider(name, Box::new(table))
+    }
+
+    /// Registers the tables of the SQLite database at `path`, or only those in `tables`, under their names.
+    /// See the `sqlite` module for how SQLite types are mapped to Arrow types.
+    #[args(tables = "None")]
+    fn register_sqlite(&mut self, path: &str, tables: Option<Vec<String>>) -> PyResult<()> {
+        let tables = match tables {
+            Some(tables) => tables,
+            None => errors::wrap(sqlite::table_names(path))?,
+        };
+        for table in tables {
+            let provider = errors::wrap(SqliteTable::try_new(path, &table))?;
+            self.register_provider(&table, Box::new(provider))?;
+        }
         Ok(())
     }
 
@@ -91,7 +576,199 @@ impl ExecutionContext {
         Ok(self.ctx.register_udf(function.function))
     }
 
-    fn tables(&self) -> HashSet<String> {
-        self.ctx.tables()
+    /// Returns the schema of the registered table `name` as a `pyarrow.Schema`.
+    fn table_schema(&self, name: &str, py: Python) -> PyResult<PyObject> {
+        let schema = match self.ctx.state.lock().unwrap().datasources.get(name) {
+            Some(provider) => provider.schema(),
+            None => {
+                return Err(
+                    errors::DataFusionError::Common(format!("No table named '{}'", name)).into(),
+                )
+            }
+        };
+        to_py::to_py_schema(&schema, py)
+    }
+
+    /// Removes the table named `name` from this context.
+    /// DataFrames already created from the table remain valid.
+    /// The `information_schema` views can't be removed.
+    fn deregister_table(&mut self, name: &str) -> PyResult<()> {
+        if information_schema::is_view(name) {
+            return Err(errors::DataFusionError::Common(format!(
+                "The view '{}' can't be deregistered",
+                name
+            ))
+            .into());
+        }
+        match self.ctx.state.lock().unwrap().datasources.remove(name) {
+            Some(_) => Ok(()),
+            None => {
+                Err(errors::DataFusionError::Common(format!("No table named '{}'", name)).into())
+            }
+        }
     }
+
+    /// Creates a schema named `name` (e.g. "staging" or "catalog.staging").
+    /// Tables are registered in it and queried via their qualified name, e.g. "staging.t".
+    fn create_schema(&mut self, name: &str) -> PyResult<()> {
+        if name == information_schema::SCHEMA || !self.schemas.insert(name.to_string()) {
+            return Err(errors::DataFusionError::Common(format!(
+                "The schema '{}' already exists",
+                name
+            ))
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Returns the names of the schemas created via `create_schema`.
+    fn schemas(&self) -> HashSet<String> {
+        self.schemas.clone()
+    }
+
+    /// Returns the names of the registered tables.
+    /// When `schema` is passed, only the (unqualified) names of the tables of that schema are returned.
+    #[args(schema = "None")]
+    fn tables(&self, schema: Option<&str>) -> PyResult<HashSet<String>> {
+        let mut tables = self.ctx.tables();
+        tables.retain(|name| !information_schema::is_view(name));
+        match schema {
+            None => Ok(tables),
+            Some(schema) if self.schemas.contains(schema) => {
+                let prefix = format!("{}.", schema);
+                Ok(tables
+                    .iter()
+                    .filter_map(|name| name.strip_prefix(&prefix))
+                    .filter(|name| !name.contains('.'))
+                    .map(|name| name.to_string())
+                    .collect())
+            }
+            Some(schema) => Err(errors::DataFusionError::Common(format!(
+                "The schema '{}' does not exist",
+                schema
+            ))
+            .into()),
+        }
+    }
+}
+
+/// a random (unique) name for a table
+pub(crate) fn random_name() -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(10)
+        .collect()
+}
+
+/// Returns the plan of the table that `register` registers, under the name it is given, in the context
+/// whose state is `state`. The plan holds a reference to the table, so it is deregistered right away:
+/// it is released once the last plan using it is dropped.
+pub(crate) fn anonymous_plan<F>(
+    state: &Arc<Mutex<ExecutionContextState>>,
+    register: F,
+) -> PyResult<LogicalPlan>
+where
+    F: FnOnce(&str) -> PyResult<()>,
+{
+    let name = random_name();
+    register(&name)?;
+    let df = _ExecutionContext::from(state.clone()).table(&name);
+    state.lock().unwrap().datasources.remove(&name);
+
+    Ok(errors::wrap(df)?.to_logical_plan())
+}
+
+/// expands `path`, a path, a glob pattern or a list of paths, into a list of paths
+fn expand_paths(path: &PyAny) -> PyResult<Vec<String>> {
+    let path = match path.extract::<String>() {
+        Ok(path) => path,
+        Err(_) => return path.extract::<Vec<String>>(),
+    };
+    if !path.contains(|c| c == '*' || c == '?' || c == '[') {
+        return Ok(vec![path]);
+    }
+
+    let error = |e: String| errors::DataFusionError::Common(e);
+    let paths = glob::glob(&path)
+        .map_err(|e| error(e.to_string()))?
+        .map(|entry| {
+            entry
+                .map(|path| path.to_string_lossy().to_string())
+                .map_err(|e| error(e.to_string()))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if paths.is_empty() {
+        return Err(error(format!("No files match '{}'", path)).into());
+    }
+    Ok(paths)
+}
+
+/// whether `path` has the extension `extension` (e.g. ".csv"), possibly followed by that of a compression
+/// (e.g. "a.csv.gz")
+fn has_extension(path: &str, extension: &str) -> bool {
+    let path = match (Compression::from_extension(path), path.rfind('.')) {
+        (Compression::Uncompressed, _) | (_, None) => path,
+        (_, Some(i)) => &path[..i],
+    };
+    path.ends_with(extension)
+}
+
+/// replaces the directories of `paths` by the files under them (except hidden ones) that have `extension`,
+/// or by all of them when `None`
+fn expand_directories(paths: Vec<String>, extension: Option<&str>) -> PyResult<Vec<String>> {
+    let mut files = vec![];
+    for path in paths {
+        if Path::new(&path).is_dir() {
+            let listed = errors::wrap(LocalStore {}.list(&path))?;
+            let matched = listed
+                .into_iter()
+                .filter(|file| extension.map_or(true, |e| has_extension(file, e)))
+                .collect::<Vec<_>>();
+            if matched.is_empty() {
+                let message = match extension {
+                    Some(extension) => {
+                        format!("No files with the extension '{}' in '{}'", extension, path)
+                    }
+                    None => format!("No files in '{}'", path),
+                };
+                return Err(errors::DataFusionError::Common(message).into());
+            }
+            files.extend(matched);
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(files)
+}
+
+/// whether any of `paths` is compressed, either with `compression` or according to its extension
+fn is_compressed(paths: &[String], compression: Option<Compression>) -> bool {
+    paths
+        .iter()
+        .any(|path| Compression::of(compression, path) != Compression::Uncompressed)
+}
+
+/// converts partitions of pyarrow `RecordBatch`es into a `MemTable`
+fn mem_table(partitions: Vec<Vec<PyObject>>, py: Python) -> PyResult<MemTable> {
+    let partitions: Vec<Vec<RecordBatch>> = partitions
+        .iter()
+        .map(|batches| {
+            batches
+                .iter()
+                .map(|batch| to_rust::to_rust_batch(batch.as_ref(py)))
+                .collect()
+        })
+        .collect::<PyResult<_>>()?;
+
+    let schema = match partitions.iter().flatten().next() {
+        Some(batch) => batch.schema(),
+        None => {
+            return Err(errors::DataFusionError::Common(
+                "At least one RecordBatch is required to infer the schema".to_string(),
+            )
+            .into())
+        }
+    };
+
+    Ok(errors::wrap(MemTable::try_new(schema, partitions))?)
 }
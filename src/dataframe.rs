@@ -1,14 +1,22 @@
+use std::collections::HashSet;
 use std::sync::{Arc, Mutex};
 
 use logical_plan::LogicalPlan;
 use pyo3::{prelude::*, types::PyTuple};
 use tokio::runtime::Runtime;
 
+use datafusion::datasource::TableProvider;
 use datafusion::execution::context::ExecutionContext as _ExecutionContext;
-use datafusion::logical_plan::{JoinType, LogicalPlanBuilder};
+use datafusion::logical_plan::{col, Expr, JoinType, LogicalPlanBuilder};
 use datafusion::physical_plan::collect;
 use datafusion::{execution::context::ExecutionContextState, logical_plan};
 
+use crate::context::anonymous_plan;
+use crate::deduplicate::{DeduplicatedTable, Keep};
+use crate::execution::plan_schema;
+use crate::join::{self, column_names};
+use crate::shared::SharedTable;
+use crate::union::UnionTable;
 use crate::{errors, to_py};
 use crate::{errors::DataFusionError, expression};
 
@@ -26,6 +34,167 @@ impl DataFrame {
     pub fn new(ctx_state: Arc<Mutex<ExecutionContextState>>, plan: LogicalPlan) -> Self {
         Self { ctx_state, plan }
     }
+
+    /// the logical plan of this DataFrame
+    pub fn plan(&self) -> &LogicalPlan {
+        &self.plan
+    }
+
+    /// Returns a DataFrame reading `provider`, as `ExecutionContext.read_parquet` does a file.
+    fn read_provider(&self, provider: Box<dyn TableProvider + Send + Sync>) -> PyResult<Self> {
+        Ok(DataFrame {
+            ctx_state: self.ctx_state.clone(),
+            plan: provider_plan(&self.ctx_state, provider)?,
+        })
+    }
+}
+
+/// the plan that reads `provider`, registered in the context `state` only while it is planned
+fn provider_plan(
+    state: &Arc<Mutex<ExecutionContextState>>,
+    provider: Box<dyn TableProvider + Send + Sync>,
+) -> PyResult<LogicalPlan> {
+    anonymous_plan(state, |name| {
+        _ExecutionContext::from(state.clone()).register_table(name, provider);
+        Ok(())
+    })
+}
+
+/// renames the columns of `plan` that are in `collisions` by appending `suffix` to their names.
+/// Returns the renamed plan and `keys` with the new names.
+fn rename_columns(
+    plan: &LogicalPlan,
+    keys: Vec<&str>,
+    collisions: &HashSet<String>,
+    suffix: &str,
+) -> datafusion::error::Result<(LogicalPlan, Vec<String>)> {
+    let renamed = |name: &str| {
+        if collisions.contains(name) {
+            format!("{}{}", name, suffix)
+        } else {
+            name.to_string()
+        }
+    };
+    let keys = keys.into_iter().map(renamed).collect();
+    if collisions.is_empty() {
+        return Ok((plan.clone(), keys));
+    }
+
+    let expressions = column_names(plan)
+        .iter()
+        .map(|name| col(name).alias(&renamed(name)))
+        .collect();
+    let plan = LogicalPlanBuilder::from(plan)
+        .project(expressions)?
+        .build()?;
+    Ok((plan, keys))
+}
+
+/// the join type of the engine for `how` (see `DataFrame.join`), or `None` for the join types
+/// that are implemented by rewrites (see the `join` module)
+fn join_type(how: &str) -> PyResult<Option<JoinType>> {
+    Ok(match how {
+        "inner" => Some(JoinType::Inner),
+        "left" => Some(JoinType::Left),
+        "right" => Some(JoinType::Right),
+        "full" | "semi" | "anti" | "cross" => None,
+        how => {
+            return Err(DataFusionError::Common(format!(
+                "The join type {} does not exist or is not implemented",
+                how
+            ))
+            .into())
+        }
+    })
+}
+
+/// The join of `left` and `right` of type `how` on the keys `left_on` and `right_on`, as in `DataFrame.join`.
+/// The tables that the join reads are planned with the context `state`.
+pub(crate) fn join_plans(
+    state: &Arc<Mutex<ExecutionContextState>>,
+    left: &LogicalPlan,
+    right: &LogicalPlan,
+    how: &str,
+    left_on: Vec<&str>,
+    right_on: Vec<&str>,
+    suffixes: (&str, &str),
+) -> PyResult<LogicalPlan> {
+    if how == "semi" || how == "anti" {
+        // only the columns of `left` are returned, so none collide
+        let plan = errors::wrap(join::semi_join(
+            left,
+            right,
+            &left_on,
+            &right_on,
+            how == "anti",
+        ))?;
+        return Ok(plan);
+    }
+
+    let right_names = column_names(right);
+
+    // the columns on both sides, other than the keys of the same name on both sides
+    let collisions = column_names(left)
+        .into_iter()
+        .filter(|name| {
+            right_names.contains(name)
+                && !left_on
+                    .iter()
+                    .zip(right_on.iter())
+                    .any(|(l, r)| l == r && *l == name.as_str())
+        })
+        .collect::<HashSet<_>>();
+
+    let (left_plan, left_on) =
+        errors::wrap(rename_columns(left, left_on, &collisions, suffixes.0))?;
+    let (right_plan, right_on) =
+        errors::wrap(rename_columns(right, right_on, &collisions, suffixes.1))?;
+
+    let left_on = left_on.iter().map(|key| key.as_str()).collect::<Vec<_>>();
+    let right_on = right_on.iter().map(|key| key.as_str()).collect::<Vec<_>>();
+
+    Ok(match (join_type(how)?, how) {
+        (Some(join_type), _) => {
+            let builder = LogicalPlanBuilder::from(&left_plan);
+            let builder = errors::wrap(builder.join(&right_plan, join_type, &left_on, &right_on))?;
+            errors::wrap(builder.build())?
+        }
+        (None, "full") => {
+            // both plans are read twice by the rewrite, but only executed once
+            let config = state.lock().unwrap().config.clone();
+            let left_plan =
+                provider_plan(state, Box::new(SharedTable::new(left_plan, config.clone())))?;
+            let right_plan = provider_plan(
+                state,
+                Box::new(SharedTable::new(right_plan, config.clone())),
+            )?;
+            let (matched, unmatched) = errors::wrap(join::full_join(
+                &left_plan,
+                &right_plan,
+                &left_on,
+                &right_on,
+            ))?;
+            let table = errors::wrap(UnionTable::try_new(matched, unmatched, true, config))?;
+            provider_plan(state, Box::new(table))?
+        }
+        _ => errors::wrap(join::cross_join(&left_plan, &right_plan))?,
+    })
+}
+
+/// converts `expressions` into sort expressions: those that are not created with `Expression.sort`
+/// are sorted in ascending order with nulls first
+fn to_sort_exprs(expressions: Vec<expression::Expression>) -> Vec<Expr> {
+    expressions
+        .into_iter()
+        .map(|e| match e.expr {
+            Expr::Sort { .. } => e.expr,
+            expr => Expr::Sort {
+                expr: Box::new(expr),
+                asc: true,
+                nulls_first: true,
+            },
+        })
+        .collect()
 }
 
 #[pymethods]
@@ -88,8 +257,102 @@ impl DataFrame {
         })
     }
 
+    /// Sorts the DataFrame by `expressions`, each created with `Expression.sort`,
+    /// e.g. `df.sort(col("a").sort(ascending=False, nulls_first=True), col("b").sort())`.
+    /// Expressions that are not sort expressions are sorted in ascending order with nulls first.
+    #[args(args = "*")]
+    fn sort(&self, args: &PyTuple) -> PyResult<Self> {
+        let expressions = to_sort_exprs(expression::from_tuple(args)?);
+        let builder = LogicalPlanBuilder::from(&self.plan);
+        let builder = errors::wrap(builder.sort(expressions))?;
+        let plan = errors::wrap(builder.build())?;
+
+        Ok(DataFrame {
+            ctx_state: self.ctx_state.clone(),
+            plan,
+        })
+    }
+
+    /// Returns the distinct rows of the DataFrame.
+    fn distinct(&self) -> PyResult<Self> {
+        let columns = self
+            .plan
+            .schema()
+            .fields()
+            .iter()
+            .map(|f| col(f.name()))
+            .collect();
+        let builder = LogicalPlanBuilder::from(&self.plan);
+        let builder = errors::wrap(builder.aggregate(columns, vec![]))?;
+        let plan = errors::wrap(builder.build())?;
+
+        Ok(DataFrame {
+            ctx_state: self.ctx_state.clone(),
+            plan,
+        })
+    }
+
+    /// Drops the rows that have the same values as a previous row on the `subset` columns
+    /// (all columns when `None`), keeping the "first" or "last" of them according to `keep`.
+    /// Rows are ordered by `order_by` (expressions as in `sort`), or by the order of the DataFrame otherwise,
+    /// which is only deterministic if the DataFrame is sorted. Nulls are equal to each other, and so are NaNs.
+    #[args(subset = "None", keep = "\"first\"", order_by = "None")]
+    fn drop_duplicates(
+        &self,
+        subset: Option<Vec<String>>,
+        keep: &str,
+        order_by: Option<Vec<expression::Expression>>,
+    ) -> PyResult<Self> {
+        let keep = errors::wrap(Keep::try_from_name(keep))?;
+        let subset = match subset {
+            Some(subset) => subset,
+            // all rows with the same values are identical, so which one is kept doesn't matter
+            None => return self.distinct(),
+        };
+
+        let plan = match order_by {
+            Some(order_by) => {
+                let builder = LogicalPlanBuilder::from(&self.plan);
+                let builder = errors::wrap(builder.sort(to_sort_exprs(order_by)))?;
+                errors::wrap(builder.build())?
+            }
+            None => self.plan.clone(),
+        };
+        let config = self.ctx_state.lock().unwrap().config.clone();
+        let table = errors::wrap(DeduplicatedTable::try_new(plan, &subset, keep, config))?;
+
+        self.read_provider(Box::new(table))
+    }
+
+    /// Returns the rows of this DataFrame followed by the rows of `other`, without duplicates when `distinct`.
+    /// Columns are matched by position and must be of the same type, unless `by_name`, in which case
+    /// they are matched by name and columns of different types are widened to a common type
+    /// (e.g. `int32` and `int64` to `int64`).
+    #[args(distinct = "false", by_name = "false")]
+    fn union(&self, other: &DataFrame, distinct: bool, by_name: bool) -> PyResult<Self> {
+        let config = self.ctx_state.lock().unwrap().config.clone();
+        let table = errors::wrap(UnionTable::try_new(
+            self.plan.clone(),
+            other.plan.clone(),
+            by_name,
+            config,
+        ))?;
+
+        let df = self.read_provider(Box::new(table))?;
+        if distinct {
+            df.distinct()
+        } else {
+            Ok(df)
+        }
+    }
+
+    /// Returns the schema of the DataFrame as a `pyarrow.Schema`.
+    fn schema(&self, py: Python) -> PyResult<PyObject> {
+        to_py::to_py_schema(&plan_schema(&self.plan), py)
+    }
+
     /// Executes the plan, returning a list of `RecordBatch`es.
-    /// Unless some order is specified in the plan, there is no guarantee of the order of the result
+    /// Unless the plan is sorted (see `sort`), there is no guarantee of the order of the result
     fn collect(&self, py: Python) -> PyResult<PyObject> {
         let ctx = _ExecutionContext::from(self.ctx_state.clone());
         let plan = ctx
@@ -110,28 +373,58 @@ impl DataFrame {
         to_py::to_py(&batches)
     }
 
-    /// Returns the join of two DataFrames `on`.
-    fn join(&self, right: &DataFrame, on: Vec<&str>, how: &str) -> PyResult<Self> {
-        let builder = LogicalPlanBuilder::from(&self.plan);
-
-        let join_type = match how {
-            "inner" => JoinType::Inner,
-            "left" => JoinType::Left,
-            "right" => JoinType::Right,
-            how => {
-                return Err(DataFusionError::Common(format!(
-                    "The join type {} does not exist or is not implemented",
-                    how
-                ))
-                .into())
+    /// Returns the join of two DataFrames on the keys `on`, columns of both DataFrames,
+    /// or on `left_on` and `right_on`, columns of this and of the `right` DataFrame respectively.
+    /// Keys of the same name are returned once. Other columns that are in both DataFrames are
+    /// returned with the `suffixes` of their side appended to their names.
+    /// `how` is one of "inner", "left", "right", "full", "semi", "anti" and "cross". "semi" and "anti"
+    /// return the rows of this DataFrame with and without a match in `right`, and "cross" (which takes no keys)
+    /// returns every combination of rows. As in SQL, null keys never match.
+    /// Full and cross joins are also available in SQL statements, while semi and anti joins are not.
+    #[args(
+        on = "None",
+        how = "\"inner\"",
+        left_on = "None",
+        right_on = "None",
+        suffixes = "(\"_l\", \"_r\")"
+    )]
+    fn join(
+        &self,
+        right: &DataFrame,
+        on: Option<Vec<&str>>,
+        how: &str,
+        left_on: Option<Vec<&str>>,
+        right_on: Option<Vec<&str>>,
+        suffixes: (&str, &str),
+    ) -> PyResult<Self> {
+        join_type(how)?;
+        let (left_on, right_on) = match (on, left_on, right_on) {
+            (None, None, None) if how == "cross" => (vec![], vec![]),
+            _ if how == "cross" => {
+                return Err(
+                    DataFusionError::Common("A cross join takes no keys".to_string()).into(),
+                )
+            }
+            (Some(on), None, None) => (on.clone(), on),
+            (None, Some(left_on), Some(right_on)) if left_on.len() == right_on.len() => {
+                (left_on, right_on)
             }
+            _ => return Err(DataFusionError::Common(
+                "Either `on` or both `left_on` and `right_on`, of the same length, are required"
+                    .to_string(),
+            )
+            .into()),
         };
 
-        let builder =
-            errors::wrap(builder.join(&right.plan, join_type, on.as_slice(), on.as_slice()))?;
-
-        let plan = errors::wrap(builder.build())?;
-
+        let plan = join_plans(
+            &self.ctx_state,
+            &self.plan,
+            &right.plan,
+            how,
+            left_on,
+            right_on,
+            suffixes,
+        )?;
         Ok(DataFrame {
             ctx_state: self.ctx_state.clone(),
             plan,
@@ -0,0 +1,196 @@
+use std::any::Any;
+use std::sync::Arc;
+
+use futures::stream;
+use pyo3::{
+    prelude::*,
+    types::{IntoPyDict, PyDict},
+};
+
+use arrow::datatypes::{Schema, SchemaRef};
+use arrow::record_batch::RecordBatch;
+
+use datafusion::datasource::datasource::{Statistics, TableProvider, TableProviderFilterPushDown};
+use datafusion::error::Result;
+use datafusion::logical_plan::{Expr, Operator};
+use datafusion::physical_plan::ExecutionPlan;
+
+use crate::errors::py_error;
+use crate::execution::{MapStream, ScanExec};
+use crate::to_py::to_py_scalar;
+use crate::to_rust::{to_rust_batch, to_rust_schema};
+
+/// A table backed by a `pyarrow.dataset.Dataset`.
+/// Projections and simple comparisons between columns and literals are passed to the
+/// dataset's scanner, and every fragment of the dataset is exposed as a partition,
+/// which is only read when it is executed.
+pub(crate) struct DatasetTable {
+    dataset: PyObject,
+    schema: SchemaRef,
+}
+
+impl DatasetTable {
+    pub fn try_new(dataset: PyObject, py: Python) -> PyResult<Self> {
+        let schema = dataset.as_ref(py).getattr("schema")?;
+        let schema = Arc::new(to_rust_schema(schema)?);
+        Ok(Self { dataset, schema })
+    }
+}
+
+/// the Python comparison method corresponding to `op`, and the one used when its operands are swapped
+fn comparison_method(op: &Operator) -> Option<(&'static str, &'static str)> {
+    Some(match op {
+        Operator::Eq => ("__eq__", "__eq__"),
+        Operator::NotEq => ("__ne__", "__ne__"),
+        Operator::Lt => ("__lt__", "__gt__"),
+        Operator::LtEq => ("__le__", "__ge__"),
+        Operator::Gt => ("__gt__", "__lt__"),
+        Operator::GtEq => ("__ge__", "__le__"),
+        _ => return None,
+    })
+}
+
+/// converts `expr` into a `pyarrow.dataset.Expression`, or `None` if it cannot be converted.
+fn to_dataset_expression(expr: &Expr, ds: &PyModule, py: Python) -> PyResult<Option<PyObject>> {
+    Ok(match expr {
+        Expr::BinaryExpr { left, op, right } => match (left.as_ref(), op, right.as_ref()) {
+            (left, Operator::And, right) => {
+                match (
+                    to_dataset_expression(left, ds, py)?,
+                    to_dataset_expression(right, ds, py)?,
+                ) {
+                    (Some(left), Some(right)) => {
+                        Some(left.call_method1(py, "__and__", (right,))?)
+                    }
+                    _ => None,
+                }
+            }
+            (Expr::Column(name), op, Expr::Literal(value)) => match comparison_method(op) {
+                Some((method, _)) => Some(
+                    ds.call1("field", (name,))?
+                        .call_method1(method, (to_py_scalar(value, py)?,))?
+                        .to_object(py),
+                ),
+                None => None,
+            },
+            (Expr::Literal(value), op, Expr::Column(name)) => match comparison_method(op) {
+                Some((_, method)) => Some(
+                    ds.call1("field", (name,))?
+                        .call_method1(method, (to_py_scalar(value, py)?,))?
+                        .to_object(py),
+                ),
+                None => None,
+            },
+            _ => None,
+        },
+        _ => None,
+    })
+}
+
+/// reads the batches of `fragment` of a dataset, scanned with the keyword arguments `kwargs`
+fn read_fragment(fragment: &PyAny, kwargs: &PyDict) -> PyResult<Vec<RecordBatch>> {
+    fragment
+        .call_method("to_table", (), Some(kwargs))?
+        .call_method0("to_batches")?
+        .iter()?
+        .map(|batch| to_rust_batch(batch?))
+        .collect()
+}
+
+impl TableProvider for DatasetTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn scan(
+        &self,
+        projection: &Option<Vec<usize>>,
+        _batch_size: usize,
+        filters: &[Expr],
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let gil = pyo3::Python::acquire_gil();
+        let py = gil.python();
+        let ds = py.import("pyarrow.dataset").map_err(py_error)?;
+
+        let (columns, projected_schema) = match projection {
+            Some(projection) => {
+                let fields = projection
+                    .iter()
+                    .map(|i| self.schema.field(*i).clone())
+                    .collect::<Vec<_>>();
+                let names = fields.iter().map(|f| f.name().clone()).collect::<Vec<_>>();
+                (Some(names), Arc::new(Schema::new(fields)))
+            }
+            None => (None, self.schema.clone()),
+        };
+
+        let mut filter: Option<PyObject> = None;
+        for expr in filters {
+            if let Some(expr) = to_dataset_expression(expr, ds, py).map_err(py_error)? {
+                filter = Some(match filter {
+                    Some(filter) => filter
+                        .call_method1(py, "__and__", (expr,))
+                        .map_err(py_error)?,
+                    None => expr,
+                });
+            }
+        }
+
+        let dataset = self.dataset.as_ref(py);
+        let schema = dataset.getattr("schema").map_err(py_error)?.to_object(py);
+        let fragments = dataset
+            .call_method1("get_fragments", (filter.clone(),))
+            .map_err(py_error)?
+            .iter()
+            .map_err(py_error)?
+            .map(|fragment| fragment.map(|fragment| fragment.to_object(py)))
+            .collect::<PyResult<Vec<PyObject>>>()
+            .map_err(py_error)?;
+
+        Ok(Arc::new(ScanExec::new(
+            "DatasetExec",
+            projected_schema.clone(),
+            fragments.len(),
+            move |partition| {
+                let gil = pyo3::Python::acquire_gil();
+                let py = gil.python();
+                let kwargs = [
+                    ("schema", schema.clone_ref(py)),
+                    ("columns", columns.to_object(py)),
+                    ("filter", filter.to_object(py)),
+                ]
+                .into_py_dict(py);
+                let batches =
+                    read_fragment(fragments[partition].as_ref(py), kwargs).map_err(py_error)?;
+
+                Ok(Box::pin(MapStream::new(
+                    projected_schema.clone(),
+                    stream::iter(batches.into_iter().map(Ok)),
+                    Ok,
+                )))
+            },
+        )))
+    }
+
+    fn statistics(&self) -> Statistics {
+        Statistics::default()
+    }
+
+    fn supports_filter_pushdown(&self, filter: &Expr) -> Result<TableProviderFilterPushDown> {
+        let gil = pyo3::Python::acquire_gil();
+        let py = gil.python();
+        let ds = py.import("pyarrow.dataset").map_err(py_error)?;
+
+        // the dataset's filters are applied with its own type coercion rules, so the engine re-applies them
+        Ok(
+            match to_dataset_expression(filter, ds, py).map_err(py_error)? {
+                Some(_) => TableProviderFilterPushDown::Inexact,
+                None => TableProviderFilterPushDown::Unsupported,
+            },
+        )
+    }
+}
@@ -0,0 +1,327 @@
+//! Tables whose rows are deduplicated on a subset of their columns, for `DataFrame.drop_duplicates`.
+use std::any::Any;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use arrow::array::{
+    as_boolean_array, as_largestring_array, as_primitive_array, as_string_array, ArrayRef,
+    BooleanArray,
+};
+use arrow::compute::{cast, filter_record_batch};
+use arrow::datatypes::{
+    DataType, Float32Type, Float64Type, Int16Type, Int32Type, Int64Type, Int8Type, SchemaRef,
+    UInt16Type, UInt32Type, UInt64Type, UInt8Type,
+};
+use arrow::error::Result as ArrowResult;
+use arrow::record_batch::RecordBatch;
+use async_trait::async_trait;
+use futures::stream::{self, StreamExt, TryStreamExt};
+
+use datafusion::datasource::datasource::{Statistics, TableProvider};
+use datafusion::error::{DataFusionError, Result};
+use datafusion::execution::context::ExecutionConfig;
+use datafusion::logical_plan::{Expr, LogicalPlan};
+use datafusion::physical_plan::{ExecutionPlan, Partitioning, SendableRecordBatchStream};
+
+use crate::execution::{
+    check_partition, create_physical_plan, plan_schema, project, project_schema, MapStream,
+};
+
+/// Which of the rows with the same values on the subset is kept
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Keep {
+    First,
+    Last,
+}
+
+impl Keep {
+    /// the option named `name`, "first" or "last"
+    pub fn try_from_name(name: &str) -> Result<Self> {
+        Ok(match name {
+            "first" => Keep::First,
+            "last" => Keep::Last,
+            other => {
+                return Err(DataFusionError::Plan(format!(
+                    "keep must be 'first' or 'last', but it is '{}'",
+                    other
+                )))
+            }
+        })
+    }
+}
+
+/// the value of a column in a row, compared by value rather than by its representation:
+/// e.g. `-0.0` and `0.0` are the same value, and so are all NaNs
+#[derive(Debug, PartialEq, Eq, Hash)]
+enum KeyValue {
+    Null,
+    Boolean(bool),
+    Int(i64),
+    UInt(u64),
+    // the bits of the value, normalized
+    Float(u64),
+    Utf8(String),
+}
+
+impl KeyValue {
+    fn float(value: f64) -> Self {
+        let value = if value == 0.0 { 0.0 } else { value };
+        if value.is_nan() {
+            KeyValue::Float(f64::NAN.to_bits())
+        } else {
+            KeyValue::Float(value.to_bits())
+        }
+    }
+}
+
+/// whether columns of type `data_type` can be in the subset
+fn is_supported(data_type: &DataType) -> bool {
+    use DataType::*;
+    matches!(
+        data_type,
+        Boolean
+            | Int8
+            | Int16
+            | Int32
+            | Int64
+            | UInt8
+            | UInt16
+            | UInt32
+            | UInt64
+            | Float32
+            | Float64
+            | Utf8
+            | LargeUtf8
+            | Timestamp(_, _)
+    )
+}
+
+/// the value of `array` in `row`, whose type is supported (see `is_supported`)
+fn key_value(array: &ArrayRef, row: usize) -> KeyValue {
+    if array.is_null(row) {
+        return KeyValue::Null;
+    }
+    match array.data_type() {
+        DataType::Boolean => KeyValue::Boolean(as_boolean_array(array).value(row)),
+        DataType::Int8 => KeyValue::Int(as_primitive_array::<Int8Type>(array).value(row) as i64),
+        DataType::Int16 => KeyValue::Int(as_primitive_array::<Int16Type>(array).value(row) as i64),
+        DataType::Int32 => KeyValue::Int(as_primitive_array::<Int32Type>(array).value(row) as i64),
+        DataType::Int64 => KeyValue::Int(as_primitive_array::<Int64Type>(array).value(row)),
+        DataType::UInt8 => KeyValue::UInt(as_primitive_array::<UInt8Type>(array).value(row) as u64),
+        DataType::UInt16 => {
+            KeyValue::UInt(as_primitive_array::<UInt16Type>(array).value(row) as u64)
+        }
+        DataType::UInt32 => {
+            KeyValue::UInt(as_primitive_array::<UInt32Type>(array).value(row) as u64)
+        }
+        DataType::UInt64 => KeyValue::UInt(as_primitive_array::<UInt64Type>(array).value(row)),
+        DataType::Float32 => {
+            KeyValue::float(as_primitive_array::<Float32Type>(array).value(row) as f64)
+        }
+        DataType::Float64 => KeyValue::float(as_primitive_array::<Float64Type>(array).value(row)),
+        DataType::Utf8 => KeyValue::Utf8(as_string_array(array).value(row).to_string()),
+        DataType::LargeUtf8 => KeyValue::Utf8(as_largestring_array(array).value(row).to_string()),
+        other => unreachable!("drop_duplicates does not support the type {:?}", other),
+    }
+}
+
+/// The rows of `batch` whose values on the columns `subset` were not `seen`, which are added to it.
+/// When `reverse`, rows are visited from the last to the first.
+fn unseen(
+    batch: &RecordBatch,
+    subset: &[usize],
+    seen: &mut HashSet<Vec<KeyValue>>,
+    reverse: bool,
+) -> ArrowResult<BooleanArray> {
+    // timestamps are compared by their underlying integer
+    let columns = subset
+        .iter()
+        .map(|i| {
+            let array = batch.column(*i);
+            match array.data_type() {
+                DataType::Timestamp(_, _) => cast(array, &DataType::Int64),
+                _ => Ok(array.clone()),
+            }
+        })
+        .collect::<ArrowResult<Vec<_>>>()?;
+
+    let rows = batch.num_rows();
+    let mut mask = vec![false; rows];
+    for i in 0..rows {
+        let row = if reverse { rows - 1 - i } else { i };
+        let key = columns.iter().map(|array| key_value(array, row)).collect();
+        mask[row] = seen.insert(key);
+    }
+    Ok(BooleanArray::from(mask))
+}
+
+/// A table with the rows of a `LogicalPlan`, of which only the first (or last) row of each
+/// distinct combination of the `subset` columns is kept. Rows are kept in the order of the plan.
+/// The plan is executed when a query that uses the table is executed (see `DeduplicateExec`).
+pub(crate) struct DeduplicatedTable {
+    plan: LogicalPlan,
+    // the indices of the columns on which rows are deduplicated
+    subset: Vec<usize>,
+    keep: Keep,
+    schema: SchemaRef,
+    config: ExecutionConfig,
+}
+
+impl DeduplicatedTable {
+    pub fn try_new(
+        plan: LogicalPlan,
+        subset: &[String],
+        keep: Keep,
+        config: ExecutionConfig,
+    ) -> Result<Self> {
+        let schema = plan_schema(&plan);
+        let subset = subset
+            .iter()
+            .map(|name| {
+                let i = schema.index_of(name)?;
+                let data_type = schema.field(i).data_type();
+                if !is_supported(data_type) {
+                    return Err(DataFusionError::NotImplemented(format!(
+                        "drop_duplicates does not support the column '{}' of type {:?}",
+                        name, data_type
+                    )));
+                }
+                Ok(i)
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self {
+            plan,
+            subset,
+            keep,
+            schema,
+            config,
+        })
+    }
+}
+
+impl TableProvider for DeduplicatedTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn scan(
+        &self,
+        projection: &Option<Vec<usize>>,
+        _batch_size: usize,
+        _filters: &[Expr],
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        // the subset is needed to deduplicate, so the plan is only projected afterwards
+        let input = create_physical_plan(&self.plan, &None, &self.config)?;
+        let (projection, schema) = project_schema(&self.schema, projection);
+
+        Ok(Arc::new(DeduplicateExec {
+            input,
+            subset: self.subset.clone(),
+            keep: self.keep,
+            projection,
+            schema,
+        }))
+    }
+
+    fn statistics(&self) -> Statistics {
+        Statistics::default()
+    }
+}
+
+/// The physical plan of a `DeduplicatedTable`, with a single partition: the partitions of its input,
+/// in order and deduplicated. When the first row is kept, batches are deduplicated as they are read;
+/// when the last row is kept, all batches are read first, since a row can only be kept once
+/// it is known to be the last.
+#[derive(Debug)]
+struct DeduplicateExec {
+    input: Arc<dyn ExecutionPlan>,
+    subset: Vec<usize>,
+    keep: Keep,
+    // the columns of the input that are returned
+    projection: Vec<usize>,
+    schema: SchemaRef,
+}
+
+#[async_trait]
+impl ExecutionPlan for DeduplicateExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![self.input.clone()]
+    }
+
+    fn with_new_children(
+        &self,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        match children.as_slice() {
+            [input] => Ok(Arc::new(DeduplicateExec {
+                input: input.clone(),
+                subset: self.subset.clone(),
+                keep: self.keep,
+                projection: self.projection.clone(),
+                schema: self.schema.clone(),
+            })),
+            _ => Err(DataFusionError::Internal(
+                "DeduplicateExec has a single child".to_string(),
+            )),
+        }
+    }
+
+    async fn execute(&self, partition: usize) -> Result<SendableRecordBatchStream> {
+        check_partition("DeduplicateExec", partition, 1)?;
+
+        let mut streams = vec![];
+        for i in 0..self.input.output_partitioning().partition_count() {
+            streams.push(self.input.execute(i).await?);
+        }
+        let input = stream::iter(streams).flatten();
+
+        let subset = self.subset.clone();
+        let projection = self.projection.clone();
+        let schema = self.schema.clone();
+        let mut seen = HashSet::new();
+        Ok(match self.keep {
+            Keep::First => Box::pin(MapStream::new(self.schema.clone(), input, move |batch| {
+                let mask = unseen(&batch, &subset, &mut seen, false)?;
+                filter_record_batch(&project(&batch, &projection, &schema)?, &mask)
+            })),
+            Keep::Last => {
+                let batches = input.try_collect::<Vec<_>>().await?;
+                // the last rows are the first ones seen from the end
+                let masks = batches
+                    .iter()
+                    .rev()
+                    .map(|batch| unseen(batch, &subset, &mut seen, true))
+                    .collect::<ArrowResult<Vec<_>>>()?;
+                let batches =
+                    batches
+                        .into_iter()
+                        .zip(masks.into_iter().rev())
+                        .map(move |(batch, mask)| {
+                            filter_record_batch(&project(&batch, &projection, &schema)?, &mask)
+                        });
+                Box::pin(MapStream::new(
+                    self.schema.clone(),
+                    stream::iter(batches),
+                    Ok,
+                ))
+            }
+        })
+    }
+}
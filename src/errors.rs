@@ -42,3 +42,8 @@ impl From<ArrowError> for DataFusionError {
 pub(crate) fn wrap<T>(a: Result<T, InnerDataFusionError>) -> Result<T, DataFusionError> {
     Ok(a?)
 }
+
+/// converts a Python error raised while executing a plan into an engine error
+pub(crate) fn py_error(err: PyErr) -> InnerDataFusionError {
+    InnerDataFusionError::Execution(format!("{}", err))
+}
@@ -0,0 +1,255 @@
+//! Helpers to execute the `LogicalPlan`s that define tables, such as views and unions,
+//! and to read the sources of tables.
+//! Plans and sources are only executed when a query that uses them is executed, never when they are scanned.
+use std::any::Any;
+use std::fmt;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::thread;
+
+use arrow::datatypes::{Schema, SchemaRef};
+use arrow::error::{ArrowError, Result as ArrowResult};
+use arrow::record_batch::RecordBatch;
+use async_trait::async_trait;
+use futures::channel::mpsc;
+use futures::executor::block_on;
+use futures::{stream, SinkExt, Stream, StreamExt};
+
+use datafusion::error::{DataFusionError, Result};
+use datafusion::execution::context::{ExecutionConfig, ExecutionContext};
+use datafusion::logical_plan::{col, LogicalPlan, LogicalPlanBuilder};
+use datafusion::physical_plan::{
+    ExecutionPlan, Partitioning, RecordBatchStream, SendableRecordBatchStream,
+};
+
+/// the schema of the rows of `plan`
+pub(crate) fn plan_schema(plan: &LogicalPlan) -> SchemaRef {
+    Arc::new(Schema::new(
+        plan.schema()
+            .fields()
+            .iter()
+            .map(|f| f.field().clone())
+            .collect(),
+    ))
+}
+
+/// the indices of the columns `projection` of `schema` (all columns when `None`), and their schema
+pub(crate) fn project_schema(
+    schema: &SchemaRef,
+    projection: &Option<Vec<usize>>,
+) -> (Vec<usize>, SchemaRef) {
+    let projection = projection
+        .clone()
+        .unwrap_or_else(|| (0..schema.fields().len()).collect());
+    let projected = Arc::new(Schema::new(
+        projection
+            .iter()
+            .map(|i| schema.field(*i).clone())
+            .collect(),
+    ));
+    (projection, projected)
+}
+
+/// errors unless `partition` is one of the `partitions` partitions of the physical plan `name`
+pub(crate) fn check_partition(name: &str, partition: usize, partitions: usize) -> Result<()> {
+    if partition >= partitions {
+        return Err(DataFusionError::Internal(format!(
+            "{} has {} partition(s), but partition {} was requested",
+            name, partitions, partition
+        )));
+    }
+    Ok(())
+}
+
+/// Optimizes `plan`, projected to the columns `projection` (or all columns when `None`),
+/// and creates its physical plan with `config`.
+/// Tables defined by plans keep a copy of the configuration of their context rather than the context:
+/// the context is locked while it plans a query, i.e. while the tables of the query are scanned.
+pub(crate) fn create_physical_plan(
+    plan: &LogicalPlan,
+    projection: &Option<Vec<usize>>,
+    config: &ExecutionConfig,
+) -> Result<Arc<dyn ExecutionPlan>> {
+    let plan = match projection {
+        Some(projection) => LogicalPlanBuilder::from(plan)
+            .project(
+                projection
+                    .iter()
+                    .map(|i| col(plan.schema().field(*i).name()))
+                    .collect(),
+            )?
+            .build()?,
+        None => plan.clone(),
+    };
+
+    let ctx = ExecutionContext::with_config(config.clone());
+    let plan = ctx.optimize(&plan)?;
+    ctx.create_physical_plan(&plan)
+}
+
+/// `batch` with only the columns `projection`, as `schema`
+pub(crate) fn project(
+    batch: &RecordBatch,
+    projection: &[usize],
+    schema: &SchemaRef,
+) -> ArrowResult<RecordBatch> {
+    RecordBatch::try_new(
+        schema.clone(),
+        projection
+            .iter()
+            .map(|i| batch.column(*i).clone())
+            .collect(),
+    )
+}
+
+/// a stream of `batches`, each with only the columns `projection`, as `schema`
+pub(crate) fn project_batches<I>(
+    batches: I,
+    projection: Vec<usize>,
+    schema: SchemaRef,
+) -> SendableRecordBatchStream
+where
+    I: Iterator<Item = ArrowResult<RecordBatch>> + Send + 'static,
+{
+    Box::pin(MapStream::new(
+        schema.clone(),
+        stream::iter(batches),
+        move |batch| project(&batch, &projection, &schema),
+    ))
+}
+
+/// A stream of batches of `schema` that `read` sends from a thread of its own, for readers that
+/// can not be moved between threads (e.g. Parquet's) or that borrow what they read (e.g. SQLite statements).
+/// At most two batches are sent ahead of the stream; `send` returns false once the stream is dropped,
+/// after which `read` should stop.
+pub(crate) fn spawn_batches<F>(schema: SchemaRef, read: F) -> SendableRecordBatchStream
+where
+    F: FnOnce(&mut dyn FnMut(ArrowResult<RecordBatch>) -> bool) -> Result<()> + Send + 'static,
+{
+    let (mut sender, receiver) = mpsc::channel(2);
+    thread::spawn(move || {
+        let mut send = |batch| block_on(sender.send(batch)).is_ok();
+        if let Err(e) = read(&mut send) {
+            send(Err(ArrowError::ExternalError(Box::new(e))));
+        }
+    });
+    Box::pin(MapStream::new(schema, receiver, Ok))
+}
+
+/// A stream of batches of `schema`: the batches of another stream, each transformed by a function.
+pub(crate) struct MapStream {
+    schema: SchemaRef,
+    input: Pin<Box<dyn Stream<Item = ArrowResult<RecordBatch>> + Send>>,
+    f: Box<dyn FnMut(RecordBatch) -> ArrowResult<RecordBatch> + Send>,
+}
+
+impl MapStream {
+    pub fn new<S, F>(schema: SchemaRef, input: S, f: F) -> Self
+    where
+        S: Stream<Item = ArrowResult<RecordBatch>> + Send + 'static,
+        F: FnMut(RecordBatch) -> ArrowResult<RecordBatch> + Send + 'static,
+    {
+        Self {
+            schema,
+            input: Box::pin(input),
+            f: Box::new(f),
+        }
+    }
+}
+
+impl Stream for MapStream {
+    type Item = ArrowResult<RecordBatch>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        this.input
+            .poll_next_unpin(cx)
+            .map(|batch| batch.map(|batch| batch.and_then(&mut this.f)))
+    }
+}
+
+impl RecordBatchStream for MapStream {
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+}
+
+// reads a partition of a `ScanExec`
+type Read = dyn Fn(usize) -> Result<SendableRecordBatchStream> + Send + Sync;
+
+/// A physical plan without children whose partitions are read by a function when they are executed,
+/// e.g. one file per partition.
+pub(crate) struct ScanExec {
+    // the name of the plan, for errors and debugging
+    name: &'static str,
+    schema: SchemaRef,
+    partitions: usize,
+    read: Arc<Read>,
+}
+
+impl ScanExec {
+    pub fn new<F>(name: &'static str, schema: SchemaRef, partitions: usize, read: F) -> Self
+    where
+        F: Fn(usize) -> Result<SendableRecordBatchStream> + Send + Sync + 'static,
+    {
+        Self {
+            name,
+            schema,
+            partitions,
+            read: Arc::new(read),
+        }
+    }
+}
+
+impl fmt::Debug for ScanExec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(self.name)
+            .field("schema", &self.schema)
+            .field("partitions", &self.partitions)
+            .finish()
+    }
+}
+
+#[async_trait]
+impl ExecutionPlan for ScanExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(self.partitions)
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![]
+    }
+
+    fn with_new_children(
+        &self,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        if children.is_empty() {
+            Ok(Arc::new(ScanExec {
+                name: self.name,
+                schema: self.schema.clone(),
+                partitions: self.partitions,
+                read: self.read.clone(),
+            }))
+        } else {
+            Err(DataFusionError::Internal(format!(
+                "{} has no children",
+                self.name
+            )))
+        }
+    }
+
+    async fn execute(&self, partition: usize) -> Result<SendableRecordBatchStream> {
+        check_partition(self.name, partition, self.partitions)?;
+        (self.read)(partition)
+    }
+}
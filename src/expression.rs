@@ -4,6 +4,8 @@ use datafusion::logical_plan::Expr as _Expr;
 use datafusion::physical_plan::udaf::AggregateUDF as _AggregateUDF;
 use datafusion::physical_plan::udf::ScalarUDF as _ScalarUDF;
 
+use crate::to_py::to_py_scalar;
+
 /// An expression that can be used on a DataFrame
 #[pyclass]
 #[derive(Debug, Clone)]
@@ -66,6 +68,10 @@ impl PyNumberProtocol for Expression {
 
 #[pyproto]
 impl PyObjectProtocol for Expression {
+    fn __repr__(&self) -> String {
+        format!("{:?}", self.expr)
+    }
+
     fn __richcmp__(&self, other: Expression, op: CompareOp) -> Expression {
         match op {
             CompareOp::Lt => Expression {
@@ -98,6 +104,96 @@ impl Expression {
             expr: self.expr.alias(name),
         })
     }
+
+    /// a sort expression of this expression, for `DataFrame.sort`
+    #[args(ascending = "true", nulls_first = "true")]
+    pub fn sort(&self, ascending: bool, nulls_first: bool) -> Expression {
+        Expression {
+            expr: _Expr::Sort {
+                expr: Box::new(self.expr.clone()),
+                asc: ascending,
+                nulls_first,
+            },
+        }
+    }
+
+    /// the kind of expression, e.g. "Column", "Literal" or "BinaryExpr"
+    #[getter]
+    fn variant(&self) -> &str {
+        match &self.expr {
+            _Expr::Alias(..) => "Alias",
+            _Expr::Column(_) => "Column",
+            _Expr::Literal(_) => "Literal",
+            _Expr::BinaryExpr { .. } => "BinaryExpr",
+            _Expr::Not(_) => "Not",
+            _Expr::IsNull(_) => "IsNull",
+            _Expr::IsNotNull(_) => "IsNotNull",
+            _Expr::Negative(_) => "Negative",
+            _Expr::Between { .. } => "Between",
+            _Expr::InList { .. } => "InList",
+            _Expr::Sort { .. } => "Sort",
+            _ => "Other",
+        }
+    }
+
+    /// the operator of a "BinaryExpr" (e.g. "=" or "<"), `None` otherwise
+    #[getter]
+    fn operator(&self) -> Option<String> {
+        match &self.expr {
+            _Expr::BinaryExpr { op, .. } => Some(op.to_string()),
+            _ => None,
+        }
+    }
+
+    /// the name of a "Column" or "Alias", `None` otherwise
+    #[getter]
+    fn name(&self) -> Option<String> {
+        match &self.expr {
+            _Expr::Column(name) | _Expr::Alias(_, name) => Some(name.clone()),
+            _ => None,
+        }
+    }
+
+    /// the value of a "Literal" as a Python object, `None` otherwise
+    #[getter]
+    fn value(&self, py: Python) -> PyResult<PyObject> {
+        match &self.expr {
+            _Expr::Literal(scalar) => to_py_scalar(scalar, py),
+            _ => Ok(py.None()),
+        }
+    }
+
+    /// the expressions this expression is composed of, e.g. the left and right side of a "BinaryExpr"
+    #[getter]
+    fn operands(&self) -> Vec<Expression> {
+        let operands = match &self.expr {
+            _Expr::Alias(expr, _)
+            | _Expr::Not(expr)
+            | _Expr::IsNull(expr)
+            | _Expr::IsNotNull(expr)
+            | _Expr::Negative(expr) => vec![expr.as_ref().clone()],
+            _Expr::BinaryExpr { left, right, .. } => {
+                vec![left.as_ref().clone(), right.as_ref().clone()]
+            }
+            _Expr::Between {
+                expr, low, high, ..
+            } => vec![
+                expr.as_ref().clone(),
+                low.as_ref().clone(),
+                high.as_ref().clone(),
+            ],
+            _Expr::InList { expr, list, .. } => {
+                let mut operands = vec![expr.as_ref().clone()];
+                operands.extend(list.iter().cloned());
+                operands
+            }
+            _ => vec![],
+        };
+        operands
+            .into_iter()
+            .map(|expr| Expression { expr })
+            .collect()
+    }
 }
 
 /// Represents a ScalarUDF
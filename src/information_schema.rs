@@ -0,0 +1,309 @@
+//! The `information_schema` views and the `SHOW` / `DESCRIBE` statements built on top of them.
+use std::any::Any;
+use std::sync::{Arc, Mutex, Weak};
+
+use arrow::array::{ArrayRef, StringArray, UInt64Array};
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use arrow::record_batch::RecordBatch;
+
+use datafusion::datasource::datasource::{Statistics, TableProvider};
+use datafusion::error::{DataFusionError, Result};
+use datafusion::execution::context::{ExecutionContext, ExecutionContextState};
+use datafusion::logical_plan::Expr;
+use datafusion::physical_plan::ExecutionPlan;
+
+use crate::execution::{project_batches, project_schema, ScanExec};
+
+pub(crate) const SCHEMA: &str = "information_schema";
+pub(crate) const TABLES: &str = "information_schema.tables";
+pub(crate) const COLUMNS: &str = "information_schema.columns";
+
+const DEFAULT_CATALOG: &str = "datafusion";
+const DEFAULT_SCHEMA: &str = "public";
+
+/// splits a (possibly qualified) table name into its catalog, schema and table
+fn split_name(name: &str) -> (String, String, String) {
+    let mut parts = name.rsplitn(3, '.');
+    let table = parts.next().unwrap_or("");
+    let schema = parts.next().unwrap_or(DEFAULT_SCHEMA);
+    let catalog = parts.next().unwrap_or(DEFAULT_CATALOG);
+    (catalog.to_string(), schema.to_string(), table.to_string())
+}
+
+/// the SQL name of `data_type`, as shown in the `data_type` column of `information_schema.columns`
+fn type_name(data_type: &DataType) -> String {
+    match data_type {
+        DataType::Null => "NULL".to_string(),
+        DataType::Boolean => "BOOLEAN".to_string(),
+        DataType::Int8 => "TINYINT".to_string(),
+        DataType::Int16 => "SMALLINT".to_string(),
+        DataType::Int32 => "INT".to_string(),
+        DataType::Int64 => "BIGINT".to_string(),
+        DataType::UInt8 => "TINYINT UNSIGNED".to_string(),
+        DataType::UInt16 => "SMALLINT UNSIGNED".to_string(),
+        DataType::UInt32 => "INT UNSIGNED".to_string(),
+        DataType::UInt64 => "BIGINT UNSIGNED".to_string(),
+        DataType::Float16 => "HALF".to_string(),
+        DataType::Float32 => "FLOAT".to_string(),
+        DataType::Float64 => "DOUBLE".to_string(),
+        DataType::Decimal(precision, scale) => format!("DECIMAL({}, {})", precision, scale),
+        DataType::Utf8 | DataType::LargeUtf8 => "VARCHAR".to_string(),
+        DataType::Binary | DataType::LargeBinary | DataType::FixedSizeBinary(_) => {
+            "BINARY".to_string()
+        }
+        DataType::Date32 | DataType::Date64 => "DATE".to_string(),
+        DataType::Time32(_) | DataType::Time64(_) => "TIME".to_string(),
+        DataType::Timestamp(_, None) => "TIMESTAMP".to_string(),
+        DataType::Timestamp(_, Some(_)) => "TIMESTAMP WITH TIME ZONE".to_string(),
+        DataType::Interval(_) | DataType::Duration(_) => "INTERVAL".to_string(),
+        DataType::List(field) | DataType::LargeList(field) | DataType::FixedSizeList(field, _) => {
+            format!("{}[]", type_name(field.data_type()))
+        }
+        DataType::Struct(fields) => format!(
+            "STRUCT<{}>",
+            fields
+                .iter()
+                .map(|f| format!("{} {}", f.name(), type_name(f.data_type())))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        DataType::Union(_) => "UNION".to_string(),
+        // dictionaries are an encoding of their values
+        DataType::Dictionary(_, values) => type_name(values),
+    }
+}
+
+fn utf8_field(name: &str) -> Field {
+    Field::new(name, DataType::Utf8, false)
+}
+
+fn tables_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        utf8_field("table_catalog"),
+        utf8_field("table_schema"),
+        utf8_field("table_name"),
+        utf8_field("table_type"),
+    ]))
+}
+
+fn columns_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        utf8_field("table_catalog"),
+        utf8_field("table_schema"),
+        utf8_field("table_name"),
+        utf8_field("column_name"),
+        Field::new("ordinal_position", DataType::UInt64, false),
+        utf8_field("data_type"),
+        utf8_field("is_nullable"),
+    ]))
+}
+
+/// Returns the rows of the `information_schema.tables` and `information_schema.columns` views describing
+/// `tables`, a list of registered tables (other than the views) and their schemas.
+fn build(mut tables: Vec<(String, SchemaRef)>) -> Result<(RecordBatch, RecordBatch)> {
+    tables.sort_by_key(|(name, _)| split_name(name));
+
+    let mut table_rows: Vec<(String, String, String, &str)> = vec![];
+    let mut column_rows: Vec<(String, String, String, String, u64, String, &str)> = vec![];
+    for (name, schema) in &tables {
+        let (catalog, schema_name, table) = split_name(name);
+        table_rows.push((
+            catalog.clone(),
+            schema_name.clone(),
+            table.clone(),
+            "BASE TABLE",
+        ));
+        for (i, field) in schema.fields().iter().enumerate() {
+            column_rows.push((
+                catalog.clone(),
+                schema_name.clone(),
+                table.clone(),
+                field.name().clone(),
+                i as u64 + 1,
+                type_name(field.data_type()),
+                if field.is_nullable() { "YES" } else { "NO" },
+            ));
+        }
+    }
+    for view in &[TABLES, COLUMNS] {
+        let (catalog, schema_name, table) = split_name(view);
+        table_rows.push((catalog, schema_name, table, "VIEW"));
+    }
+
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(StringArray::from(
+            table_rows.iter().map(|r| r.0.as_str()).collect::<Vec<_>>(),
+        )),
+        Arc::new(StringArray::from(
+            table_rows.iter().map(|r| r.1.as_str()).collect::<Vec<_>>(),
+        )),
+        Arc::new(StringArray::from(
+            table_rows.iter().map(|r| r.2.as_str()).collect::<Vec<_>>(),
+        )),
+        Arc::new(StringArray::from(
+            table_rows.iter().map(|r| r.3).collect::<Vec<_>>(),
+        )),
+    ];
+    let tables = RecordBatch::try_new(tables_schema(), columns)?;
+
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(StringArray::from(
+            column_rows.iter().map(|r| r.0.as_str()).collect::<Vec<_>>(),
+        )),
+        Arc::new(StringArray::from(
+            column_rows.iter().map(|r| r.1.as_str()).collect::<Vec<_>>(),
+        )),
+        Arc::new(StringArray::from(
+            column_rows.iter().map(|r| r.2.as_str()).collect::<Vec<_>>(),
+        )),
+        Arc::new(StringArray::from(
+            column_rows.iter().map(|r| r.3.as_str()).collect::<Vec<_>>(),
+        )),
+        Arc::new(UInt64Array::from(
+            column_rows.iter().map(|r| r.4).collect::<Vec<_>>(),
+        )),
+        Arc::new(StringArray::from(
+            column_rows.iter().map(|r| r.5.as_str()).collect::<Vec<_>>(),
+        )),
+        Arc::new(StringArray::from(
+            column_rows.iter().map(|r| r.6).collect::<Vec<_>>(),
+        )),
+    ];
+    let columns = RecordBatch::try_new(columns_schema(), columns)?;
+
+    Ok((tables, columns))
+}
+
+/// Registers the `information_schema.tables` and `information_schema.columns` views
+/// in the context whose state is `state`.
+pub(crate) fn register(state: &Arc<Mutex<ExecutionContextState>>) {
+    let mut ctx = ExecutionContext::from(state.clone());
+    for (name, view) in &[(TABLES, View::Tables), (COLUMNS, View::Columns)] {
+        let table = InformationSchemaTable {
+            view: *view,
+            state: Arc::downgrade(state),
+        };
+        ctx.register_table(name, Box::new(table));
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum View {
+    Tables,
+    Columns,
+}
+
+/// An `information_schema` view, which describes the tables registered in the context
+/// when a query that uses it is executed.
+struct InformationSchemaTable {
+    view: View,
+    // the context holds this table, so it is only referenced weakly
+    state: Weak<Mutex<ExecutionContextState>>,
+}
+
+impl TableProvider for InformationSchemaTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        match self.view {
+            View::Tables => tables_schema(),
+            View::Columns => columns_schema(),
+        }
+    }
+
+    fn scan(
+        &self,
+        projection: &Option<Vec<usize>>,
+        _batch_size: usize,
+        _filters: &[Expr],
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        // the context is locked while it plans queries, so it is only read during execution
+        let (projection, schema) = project_schema(&self.schema(), projection);
+        let view = self.view;
+        let state = self.state.clone();
+
+        Ok(Arc::new(ScanExec::new(
+            "InformationSchemaExec",
+            schema.clone(),
+            1,
+            move |_| {
+                let state = state.upgrade().ok_or_else(|| {
+                    DataFusionError::Execution(
+                        "The context of the view no longer exists".to_string(),
+                    )
+                })?;
+                let tables = state
+                    .lock()
+                    .unwrap()
+                    .datasources
+                    .iter()
+                    .filter(|(name, _)| !is_view(name))
+                    .map(|(name, provider)| (name.clone(), provider.schema()))
+                    .collect();
+                let (tables, columns) = build(tables)?;
+                let batch = match view {
+                    View::Tables => tables,
+                    View::Columns => columns,
+                };
+
+                Ok(project_batches(
+                    vec![Ok(batch)].into_iter(),
+                    projection.clone(),
+                    schema.clone(),
+                ))
+            },
+        )))
+    }
+
+    fn statistics(&self) -> Statistics {
+        Statistics::default()
+    }
+}
+
+/// whether the table `name` is one of the `information_schema` views
+pub(crate) fn is_view(name: &str) -> bool {
+    name == TABLES || name == COLUMNS
+}
+
+/// whether the (possibly qualified) table names `left` and `right` refer to the same table
+pub(crate) fn is_same_table(left: &str, right: &str) -> bool {
+    split_name(left) == split_name(right)
+}
+
+/// Rewrites `SHOW TABLES`, `SHOW COLUMNS FROM <table>` and `DESCRIBE <table>` into
+/// queries over the `information_schema` views, along with the table they describe, if any.
+/// Returns `None` for any other statement.
+pub(crate) fn rewrite(query: &str) -> Option<(String, Option<String>)> {
+    let query = query.trim().trim_end_matches(';');
+    let tokens = query.split_whitespace().collect::<Vec<_>>();
+    let keywords = tokens.iter().map(|t| t.to_uppercase()).collect::<Vec<_>>();
+    let keywords = keywords.iter().map(|t| t.as_str()).collect::<Vec<_>>();
+
+    let table = match keywords.as_slice() {
+        ["SHOW", "TABLES"] => {
+            return Some((
+                format!(
+                    "SELECT table_catalog, table_schema, table_name, table_type FROM {}",
+                    TABLES
+                ),
+                None,
+            ))
+        }
+        ["SHOW", "COLUMNS", "FROM", _] => tokens[3],
+        ["DESCRIBE", _] => tokens[1],
+        _ => return None,
+    };
+
+    let (catalog, schema, name) = split_name(table);
+    let query = format!(
+        "SELECT column_name, data_type, is_nullable FROM {} \
+         WHERE table_catalog = '{}' AND table_schema = '{}' AND table_name = '{}'",
+        COLUMNS,
+        catalog.replace('\'', "''"),
+        schema.replace('\'', "''"),
+        name.replace('\'', "''"),
+    );
+    Some((query, Some(table.to_string())))
+}
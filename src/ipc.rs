@@ -0,0 +1,95 @@
+use std::any::Any;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::sync::Arc;
+
+use arrow::datatypes::SchemaRef;
+use arrow::error::Result as ArrowResult;
+use arrow::ipc::reader::{FileReader, StreamReader};
+use arrow::record_batch::RecordBatch;
+
+use datafusion::datasource::datasource::{Statistics, TableProvider};
+use datafusion::error::Result;
+use datafusion::logical_plan::Expr;
+use datafusion::physical_plan::ExecutionPlan;
+
+use crate::execution::{project_batches, project_schema, ScanExec};
+
+// the first bytes of a file in the IPC file format
+const MAGIC: &[u8; 6] = b"ARROW1";
+
+/// A table backed by a file in the Arrow IPC file format (also known as Feather v2)
+/// or in the Arrow IPC stream format. The file is read batch by batch when a query that uses
+/// the table is executed.
+pub(crate) struct IpcTable {
+    path: String,
+    is_file_format: bool,
+    schema: SchemaRef,
+}
+
+impl IpcTable {
+    /// Creates a new table from the file at `path`, whose format is detected from its first bytes.
+    pub fn try_new(path: &str) -> Result<Self> {
+        let mut magic = [0; 6];
+        let is_file_format = match File::open(path)?.read_exact(&mut magic) {
+            Ok(()) => &magic == MAGIC,
+            Err(_) => false,
+        };
+
+        let reader = BufReader::new(File::open(path)?);
+        let schema = if is_file_format {
+            FileReader::try_new(reader)?.schema()
+        } else {
+            StreamReader::try_new(reader)?.schema()
+        };
+
+        Ok(Self {
+            path: path.to_string(),
+            is_file_format,
+            schema,
+        })
+    }
+}
+
+impl TableProvider for IpcTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn scan(
+        &self,
+        projection: &Option<Vec<usize>>,
+        _batch_size: usize,
+        _filters: &[Expr],
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let (projection, schema) = project_schema(&self.schema, projection);
+        let path = self.path.clone();
+        let is_file_format = self.is_file_format;
+
+        // the IPC readers of this version of Arrow neither take a projection nor memory-map the file,
+        // so every column of each batch is read and the batch is projected afterwards
+        Ok(Arc::new(ScanExec::new(
+            "IpcExec",
+            schema.clone(),
+            1,
+            move |_| {
+                let reader = BufReader::new(File::open(&path)?);
+                let batches: Box<dyn Iterator<Item = ArrowResult<RecordBatch>> + Send> =
+                    if is_file_format {
+                        Box::new(FileReader::try_new(reader)?)
+                    } else {
+                        Box::new(StreamReader::try_new(reader)?)
+                    };
+                Ok(project_batches(batches, projection.clone(), schema.clone()))
+            },
+        )))
+    }
+
+    fn statistics(&self) -> Statistics {
+        Statistics::default()
+    }
+}
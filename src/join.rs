@@ -0,0 +1,115 @@
+//! Joins that the engine does not implement (full, semi, anti and cross), rewritten into the ones it does.
+//! As in SQL, null keys never match: e.g. an anti join returns the rows of the left side whose keys are null.
+use datafusion::error::Result;
+use datafusion::logical_plan::{col, Expr, JoinType, LogicalPlan, LogicalPlanBuilder};
+use datafusion::scalar::ScalarValue;
+
+// the name of the columns added to plans to tell matched rows apart or to join all rows
+const MARKER: &str = "__datafusion_join_marker";
+
+/// the names of the columns of `plan`
+pub(crate) fn column_names(plan: &LogicalPlan) -> Vec<String> {
+    plan.schema()
+        .fields()
+        .iter()
+        .map(|f| f.name().clone())
+        .collect()
+}
+
+/// `plan` with the column `MARKER`, whose value is `value` on every row
+fn with_marker(plan: &LogicalPlan, value: ScalarValue) -> Result<LogicalPlan> {
+    let mut expressions = column_names(plan)
+        .iter()
+        .map(|name| col(name))
+        .collect::<Vec<_>>();
+    expressions.push(Expr::Literal(value).alias(MARKER));
+    LogicalPlanBuilder::from(plan).project(expressions)?.build()
+}
+
+/// projects `builder` to its columns other than `MARKER`
+fn without_marker(builder: LogicalPlanBuilder) -> Result<LogicalPlanBuilder> {
+    let plan = builder.build()?;
+    let expressions = column_names(&plan)
+        .iter()
+        .filter(|name| *name != MARKER)
+        .map(|name| col(name))
+        .collect();
+    LogicalPlanBuilder::from(&plan).project(expressions)
+}
+
+/// The rows of `left` that have (or, when `anti`, that do not have) a matching row in `right`.
+/// Only the columns of `left` are returned, and each of its rows is returned at most once.
+pub(crate) fn semi_join(
+    left: &LogicalPlan,
+    right: &LogicalPlan,
+    left_on: &[&str],
+    right_on: &[&str],
+    anti: bool,
+) -> Result<LogicalPlan> {
+    // the distinct keys of `right`, renamed so that they don't collide with the columns of `left`
+    let keys = (0..right_on.len())
+        .map(|i| format!("{}_{}", MARKER, i))
+        .collect::<Vec<_>>();
+    let mut builder = LogicalPlanBuilder::from(right);
+    for key in right_on {
+        builder = builder.filter(Expr::IsNotNull(Box::new(col(key))))?;
+    }
+    let right = builder
+        .aggregate(right_on.iter().map(|key| col(key)).collect(), vec![])?
+        .build()?;
+    let mut expressions = right_on
+        .iter()
+        .zip(keys.iter())
+        .map(|(key, name)| col(key).alias(name))
+        .collect::<Vec<_>>();
+    expressions.push(Expr::Literal(ScalarValue::Boolean(Some(true))).alias(MARKER));
+    let right = LogicalPlanBuilder::from(&right)
+        .project(expressions)?
+        .build()?;
+
+    let keys = keys.iter().map(|key| key.as_str()).collect::<Vec<_>>();
+    let builder = if anti {
+        // rows without a match are those whose marker is null after a left join
+        LogicalPlanBuilder::from(left)
+            .join(&right, JoinType::Left, left_on, &keys)?
+            .filter(Expr::IsNull(Box::new(col(MARKER))))?
+    } else {
+        LogicalPlanBuilder::from(left).join(&right, JoinType::Inner, left_on, &keys)?
+    };
+    builder
+        .project(column_names(left).iter().map(|name| col(name)).collect())?
+        .build()
+}
+
+/// The full outer join of `left` and `right`, as two plans whose union (by name) is the join:
+/// the left join of `left` and `right`, and the rows of `right` without a match in `left`.
+/// Both plans read `left` and `right`, which are best wrapped in a `SharedTable` to execute them once.
+pub(crate) fn full_join(
+    left: &LogicalPlan,
+    right: &LogicalPlan,
+    left_on: &[&str],
+    right_on: &[&str],
+) -> Result<(LogicalPlan, LogicalPlan)> {
+    let left = with_marker(left, ScalarValue::Boolean(Some(true)))?;
+
+    let matched = LogicalPlanBuilder::from(&left).join(right, JoinType::Left, left_on, right_on)?;
+    let matched = without_marker(matched)?.build()?;
+
+    // rows of `right` without a match are those whose marker is null after a right join
+    let unmatched = LogicalPlanBuilder::from(&left)
+        .join(right, JoinType::Right, left_on, right_on)?
+        .filter(Expr::IsNull(Box::new(col(MARKER))))?;
+    let unmatched = without_marker(unmatched)?.build()?;
+
+    Ok((matched, unmatched))
+}
+
+/// The cartesian product of `left` and `right`, joined on a constant key.
+pub(crate) fn cross_join(left: &LogicalPlan, right: &LogicalPlan) -> Result<LogicalPlan> {
+    let left = with_marker(left, ScalarValue::Int64(Some(0)))?;
+    let right = with_marker(right, ScalarValue::Int64(Some(0)))?;
+
+    let builder =
+        LogicalPlanBuilder::from(&left).join(&right, JoinType::Inner, &[MARKER], &[MARKER])?;
+    without_marker(builder)?.build()
+}
@@ -0,0 +1,100 @@
+use std::any::Any;
+use std::fs::File;
+use std::io::BufReader;
+use std::sync::Arc;
+
+use arrow::datatypes::{Schema, SchemaRef};
+use arrow::json::reader::{infer_json_schema, Reader};
+
+use datafusion::datasource::datasource::{Statistics, TableProvider};
+use datafusion::error::Result;
+use datafusion::logical_plan::Expr;
+use datafusion::physical_plan::ExecutionPlan;
+
+use crate::execution::{project_schema, spawn_batches, ScanExec};
+
+/// A table backed by a newline-delimited JSON file.
+/// The file is read when the table's scan is executed, parsing only the projected columns.
+pub(crate) struct JsonTable {
+    path: String,
+    schema: SchemaRef,
+}
+
+impl JsonTable {
+    /// Creates a new table from the file at `path`. When `schema` is `None`, it is inferred
+    /// from the first `schema_infer_max_records` lines of the file.
+    pub fn try_new(
+        path: &str,
+        schema: Option<Schema>,
+        schema_infer_max_records: usize,
+    ) -> Result<Self> {
+        let schema = match schema {
+            Some(schema) => Arc::new(schema),
+            None => {
+                let mut reader = BufReader::new(File::open(path)?);
+                infer_json_schema(&mut reader, Some(schema_infer_max_records))?
+            }
+        };
+        Ok(Self {
+            path: path.to_string(),
+            schema,
+        })
+    }
+}
+
+impl TableProvider for JsonTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn scan(
+        &self,
+        projection: &Option<Vec<usize>>,
+        batch_size: usize,
+        _filters: &[Expr],
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let (_, projected_schema) = project_schema(&self.schema, projection);
+        let names = projection.as_ref().map(|_| {
+            projected_schema
+                .fields()
+                .iter()
+                .map(|f| f.name().clone())
+                .collect::<Vec<_>>()
+        });
+
+        let path = self.path.clone();
+        let schema = self.schema.clone();
+        Ok(Arc::new(ScanExec::new(
+            "JsonExec",
+            projected_schema.clone(),
+            1,
+            move |_| {
+                let path = path.clone();
+                let schema = schema.clone();
+                let names = names.clone();
+                Ok(spawn_batches(projected_schema.clone(), move |send| {
+                    let mut reader = Reader::new(
+                        BufReader::new(File::open(&path)?),
+                        schema,
+                        batch_size,
+                        names,
+                    );
+                    while let Some(batch) = reader.next()? {
+                        if !send(Ok(batch)) {
+                            break;
+                        }
+                    }
+                    Ok(())
+                }))
+            },
+        )))
+    }
+
+    fn statistics(&self) -> Statistics {
+        Statistics::default()
+    }
+}
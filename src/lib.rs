@@ -1,16 +1,34 @@
 use pyo3::prelude::*;
 
+mod avro;
+mod compression;
 mod context;
 mod dataframe;
+mod dataset;
+mod deduplicate;
 mod errors;
+mod execution;
 mod expression;
 mod functions;
+mod information_schema;
+mod ipc;
+mod join;
+mod json;
+mod listing;
+mod multi_file;
+mod object_store;
+mod provider;
 mod scalar;
+mod shared;
+mod sql;
+mod sqlite;
 mod to_py;
 mod to_rust;
 mod types;
 mod udaf;
 mod udf;
+mod union;
+mod view;
 
 /// DataFusion.
 #[pymodule]
@@ -18,6 +36,8 @@ fn datafusion(py: Python, m: &PyModule) -> PyResult<()> {
     m.add_class::<context::ExecutionContext>()?;
     m.add_class::<dataframe::DataFrame>()?;
     m.add_class::<expression::Expression>()?;
+    m.add_class::<object_store::InMemoryObjectStore>()?;
+    m.add_class::<object_store::LocalFileSystem>()?;
 
     let functions = PyModule::new(py, "functions")?;
     functions::init(functions)?;
@@ -0,0 +1,403 @@
+//! Tables backed by a directory of Parquet files partitioned in the Hive style, e.g.
+//! `year=2026/month=10/part-0.parquet`, whose partition keys are exposed as columns.
+use std::any::Any;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+
+use arrow::array::ArrayRef;
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use arrow::error::Result as ArrowResult;
+use arrow::record_batch::RecordBatch;
+
+use datafusion::datasource::datasource::{Statistics, TableProvider, TableProviderFilterPushDown};
+use datafusion::error::{DataFusionError, Result};
+use datafusion::logical_plan::{Expr, Operator};
+use datafusion::physical_plan::ExecutionPlan;
+use datafusion::scalar::ScalarValue;
+
+use crate::execution::{project_schema, spawn_batches, ScanExec};
+use crate::multi_file::{align, merge_schemas, FileFormat};
+
+/// the directory name of the partitions whose key is null
+const DEFAULT_PARTITION: &str = "__HIVE_DEFAULT_PARTITION__";
+
+/// a data file and the values of its partition keys, in the order of the table's partition columns
+#[derive(Clone)]
+struct PartitionedFile {
+    path: String,
+    values: Vec<ScalarValue>,
+}
+
+/// lists the files with `extension` under `dir` recursively, ignoring hidden files such as `_SUCCESS`
+fn list_files(dir: &Path, extension: &str, files: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        if name.starts_with('.') || name.starts_with('_') {
+            continue;
+        }
+        if path.is_dir() {
+            list_files(&path, extension, files)?;
+        } else if name.ends_with(extension) {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// the `key=value` pairs of the directories between `root` and `path`
+fn partition_pairs(root: &Path, path: &Path) -> Vec<(String, String)> {
+    path.parent()
+        .and_then(|parent| parent.strip_prefix(root).ok())
+        .map(|dirs| {
+            dirs.iter()
+                .filter_map(|dir| {
+                    let mut pair = dir.to_str()?.splitn(2, '=');
+                    Some((pair.next()?.to_string(), pair.next()?.to_string()))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn parse<T: FromStr>(value: &str, data_type: &DataType) -> Result<T> {
+    value.parse().map_err(|_| {
+        DataFusionError::Plan(format!(
+            "The partition value '{}' is not a valid {:?}",
+            value, data_type
+        ))
+    })
+}
+
+/// parses a partition value of a directory name as `data_type`, `None` being the null value
+fn parse_value(value: Option<&str>, data_type: &DataType) -> Result<ScalarValue> {
+    Ok(match data_type {
+        DataType::Boolean => ScalarValue::Boolean(value.map(|v| parse(v, data_type)).transpose()?),
+        DataType::Int8 => ScalarValue::Int8(value.map(|v| parse(v, data_type)).transpose()?),
+        DataType::Int16 => ScalarValue::Int16(value.map(|v| parse(v, data_type)).transpose()?),
+        DataType::Int32 => ScalarValue::Int32(value.map(|v| parse(v, data_type)).transpose()?),
+        DataType::Int64 => ScalarValue::Int64(value.map(|v| parse(v, data_type)).transpose()?),
+        DataType::UInt8 => ScalarValue::UInt8(value.map(|v| parse(v, data_type)).transpose()?),
+        DataType::UInt16 => ScalarValue::UInt16(value.map(|v| parse(v, data_type)).transpose()?),
+        DataType::UInt32 => ScalarValue::UInt32(value.map(|v| parse(v, data_type)).transpose()?),
+        DataType::UInt64 => ScalarValue::UInt64(value.map(|v| parse(v, data_type)).transpose()?),
+        DataType::Float32 => ScalarValue::Float32(value.map(|v| parse(v, data_type)).transpose()?),
+        DataType::Float64 => ScalarValue::Float64(value.map(|v| parse(v, data_type)).transpose()?),
+        DataType::Utf8 => ScalarValue::Utf8(value.map(|v| v.to_string())),
+        DataType::LargeUtf8 => ScalarValue::LargeUtf8(value.map(|v| v.to_string())),
+        other => {
+            return Err(DataFusionError::NotImplemented(format!(
+                "Partition columns of type {:?} are not supported",
+                other
+            )))
+        }
+    })
+}
+
+fn as_f64(value: &ScalarValue) -> Option<f64> {
+    Some(match value {
+        ScalarValue::Int8(Some(v)) => *v as f64,
+        ScalarValue::Int16(Some(v)) => *v as f64,
+        ScalarValue::Int32(Some(v)) => *v as f64,
+        ScalarValue::Int64(Some(v)) => *v as f64,
+        ScalarValue::UInt8(Some(v)) => *v as f64,
+        ScalarValue::UInt16(Some(v)) => *v as f64,
+        ScalarValue::UInt32(Some(v)) => *v as f64,
+        ScalarValue::UInt64(Some(v)) => *v as f64,
+        ScalarValue::Float32(Some(v)) => *v as f64,
+        ScalarValue::Float64(Some(v)) => *v,
+        _ => return None,
+    })
+}
+
+fn compare(left: &ScalarValue, right: &ScalarValue) -> Option<Ordering> {
+    match (as_f64(left), as_f64(right)) {
+        (Some(left), Some(right)) => left.partial_cmp(&right),
+        _ => match (left, right) {
+            (ScalarValue::Utf8(Some(left)), ScalarValue::Utf8(Some(right)))
+            | (ScalarValue::LargeUtf8(Some(left)), ScalarValue::Utf8(Some(right)))
+            | (ScalarValue::Utf8(Some(left)), ScalarValue::LargeUtf8(Some(right)))
+            | (ScalarValue::LargeUtf8(Some(left)), ScalarValue::LargeUtf8(Some(right))) => {
+                Some(left.cmp(right))
+            }
+            (ScalarValue::Boolean(Some(left)), ScalarValue::Boolean(Some(right))) => {
+                Some(left.cmp(right))
+            }
+            _ => None,
+        },
+    }
+}
+
+/// evaluates the predicate `expr` from the partition `values` of a file.
+/// Returns `None` when `expr` cannot be evaluated from them alone.
+fn evaluate(expr: &Expr, values: &HashMap<&str, &ScalarValue>) -> Option<bool> {
+    let scalar = |expr: &Expr| match expr {
+        Expr::Column(name) => values.get(name.as_str()).map(|v| (*v).clone()),
+        Expr::Literal(value) => Some(value.clone()),
+        _ => None,
+    };
+    match expr {
+        Expr::BinaryExpr { left, op, right } => match op {
+            Operator::And => match (evaluate(left, values), evaluate(right, values)) {
+                (Some(false), _) | (_, Some(false)) => Some(false),
+                (Some(true), Some(true)) => Some(true),
+                _ => None,
+            },
+            Operator::Or => match (evaluate(left, values), evaluate(right, values)) {
+                (Some(true), _) | (_, Some(true)) => Some(true),
+                (Some(false), Some(false)) => Some(false),
+                _ => None,
+            },
+            op => {
+                let ordering = compare(&scalar(left)?, &scalar(right)?)?;
+                Some(match op {
+                    Operator::Eq => ordering == Ordering::Equal,
+                    Operator::NotEq => ordering != Ordering::Equal,
+                    Operator::Lt => ordering == Ordering::Less,
+                    Operator::LtEq => ordering != Ordering::Greater,
+                    Operator::Gt => ordering == Ordering::Greater,
+                    Operator::GtEq => ordering != Ordering::Less,
+                    _ => return None,
+                })
+            }
+        },
+        Expr::Not(expr) => evaluate(expr, values).map(|v| !v),
+        _ => None,
+    }
+}
+
+/// A table backed by the Parquet files under a directory partitioned in the Hive style.
+/// Partition keys are exposed as nullable columns after the columns of the files, whose schemas are merged
+/// as in `MultiFileTable`, and the partitions named `__HIVE_DEFAULT_PARTITION__` are null.
+/// Each file is a partition of the table, read when it is executed, and files whose
+/// partition values do not satisfy the filters of a query are not read.
+pub(crate) struct ListingTable {
+    files: Vec<PartitionedFile>,
+    file_schema: SchemaRef,
+    schema: SchemaRef,
+}
+
+impl ListingTable {
+    /// Creates a new table from the files with `file_extension` under `path`.
+    /// `partitioning` is the name and type of each partition key; when `None`, the keys are read
+    /// from the directories and their types inferred (`Int64` if every value is an integer, `Utf8` otherwise).
+    pub fn try_new(
+        path: &str,
+        partitioning: Option<Vec<(String, DataType)>>,
+        file_extension: &str,
+    ) -> Result<Self> {
+        let root = Path::new(path);
+        let mut paths = vec![];
+        list_files(root, file_extension, &mut paths)?;
+        paths.sort();
+
+        if paths.is_empty() {
+            return Err(DataFusionError::Plan(format!(
+                "No files with extension '{}' under '{}'",
+                file_extension, path
+            )));
+        }
+
+        let pairs = paths
+            .iter()
+            .map(|path| partition_pairs(root, path))
+            .collect::<Vec<_>>();
+        // the value of the partition key `key` of the `i`-th file, or `None` when it has no such key
+        let value = |i: usize, key: &str| {
+            pairs[i]
+                .iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, value)| value.as_str())
+        };
+
+        let partitioning = match partitioning {
+            Some(partitioning) => partitioning,
+            None => pairs[0]
+                .iter()
+                .map(|(key, _)| {
+                    let is_integer = (0..paths.len()).all(|i| match value(i, key) {
+                        Some(DEFAULT_PARTITION) => true,
+                        Some(value) => value.parse::<i64>().is_ok(),
+                        None => false,
+                    });
+                    let data_type = if is_integer {
+                        DataType::Int64
+                    } else {
+                        DataType::Utf8
+                    };
+                    (key.clone(), data_type)
+                })
+                .collect(),
+        };
+
+        let paths = paths
+            .iter()
+            .map(|path| path.to_string_lossy().into_owned())
+            .collect::<Vec<_>>();
+        let files = paths
+            .iter()
+            .enumerate()
+            .map(|(i, path)| {
+                let values = partitioning
+                    .iter()
+                    .map(|(key, data_type)| {
+                        let value = value(i, key).ok_or_else(|| {
+                            DataFusionError::Plan(format!(
+                                "The file '{}' has no partition key '{}'",
+                                path, key
+                            ))
+                        })?;
+                        match value {
+                            DEFAULT_PARTITION => parse_value(None, data_type),
+                            value => parse_value(Some(value), data_type),
+                        }
+                    })
+                    .collect::<Result<_>>()?;
+                Ok(PartitionedFile {
+                    path: path.clone(),
+                    values,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let schemas = paths
+            .iter()
+            .map(|path| FileFormat::Parquet.infer_schema(&None, path))
+            .collect::<Result<Vec<_>>>()?;
+        let file_schema = merge_schemas(&paths, &schemas)?;
+
+        let mut fields = file_schema.fields().clone();
+        fields.extend(
+            partitioning
+                .iter()
+                .map(|(key, data_type)| Field::new(key, data_type.clone(), true)),
+        );
+
+        Ok(Self {
+            files,
+            file_schema: Arc::new(file_schema),
+            schema: Arc::new(Schema::new(fields)),
+        })
+    }
+}
+
+/// Reads the `projection` of the columns of `schema`, the schema of a listing table whose first `file_columns`
+/// columns are those of its files, from `file` in batches of `batch_size` rows, and sends each batch to `send`.
+fn read(
+    file: &PartitionedFile,
+    schema: &SchemaRef,
+    file_columns: usize,
+    projection: &[usize],
+    batch_size: usize,
+    send: &mut dyn FnMut(ArrowResult<RecordBatch>) -> bool,
+) -> Result<()> {
+    let projected_schema = Arc::new(Schema::new(
+        projection
+            .iter()
+            .map(|i| schema.field(*i).clone())
+            .collect(),
+    ));
+    // the projected columns of the files
+    let file_schema = Arc::new(Schema::new(
+        projection
+            .iter()
+            .filter(|i| **i < file_columns)
+            .map(|i| schema.field(*i).clone())
+            .collect(),
+    ));
+    let names = file_schema
+        .fields()
+        .iter()
+        .map(|f| f.name().clone())
+        .collect::<Vec<_>>();
+
+    let batches =
+        FileFormat::Parquet.read(&None, &file.path, file_schema.clone(), &names, batch_size)?;
+    for batch in batches {
+        let batch = batch.and_then(|batch| {
+            let length = batch.num_rows();
+            let mut columns = if names.is_empty() {
+                vec![]
+            } else {
+                align(batch, file_schema.clone())?.columns().to_vec()
+            }
+            .into_iter();
+            let columns = projection
+                .iter()
+                .map(|i| match i.checked_sub(file_columns) {
+                    Some(key) => file.values[key].to_array_of_size(length),
+                    None => columns.next().unwrap(),
+                })
+                .collect::<Vec<ArrayRef>>();
+            RecordBatch::try_new(projected_schema.clone(), columns)
+        });
+        if !send(batch) {
+            break;
+        }
+    }
+    Ok(())
+}
+
+impl TableProvider for ListingTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn scan(
+        &self,
+        projection: &Option<Vec<usize>>,
+        batch_size: usize,
+        filters: &[Expr],
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let (projection, projected_schema) = project_schema(&self.schema, projection);
+
+        let file_columns = self.file_schema.fields().len();
+        let files = self
+            .files
+            .iter()
+            .filter(|file| {
+                let values = self.schema.fields()[file_columns..]
+                    .iter()
+                    .map(|f| f.name().as_str())
+                    .zip(file.values.iter())
+                    .collect::<HashMap<_, _>>();
+                filters
+                    .iter()
+                    .all(|filter| evaluate(filter, &values) != Some(false))
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+
+        let schema = self.schema.clone();
+        Ok(Arc::new(ScanExec::new(
+            "ListingExec",
+            projected_schema.clone(),
+            files.len(),
+            move |partition| {
+                let file = files[partition].clone();
+                let schema = schema.clone();
+                let projection = projection.clone();
+                Ok(spawn_batches(projected_schema.clone(), move |send| {
+                    read(&file, &schema, file_columns, &projection, batch_size, send)
+                }))
+            },
+        )))
+    }
+
+    fn statistics(&self) -> Statistics {
+        Statistics::default()
+    }
+
+    fn supports_filter_pushdown(&self, _filter: &Expr) -> Result<TableProviderFilterPushDown> {
+        // filters are only used to skip files, so the engine re-applies them
+        Ok(TableProviderFilterPushDown::Inexact)
+    }
+}
@@ -0,0 +1,341 @@
+//! Tables backed by a list of files (e.g. the result of a glob pattern) whose schemas are merged.
+//! CSV and newline-delimited JSON files can be compressed, in which case they are decompressed while read.
+use std::any::Any;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Cursor, Read};
+use std::iter;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use arrow::array::{new_null_array, ArrayRef};
+use arrow::compute::cast;
+use arrow::csv::reader::{infer_file_schema, Reader as CsvReader};
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use arrow::error::Result as ArrowResult;
+use arrow::json::reader::{infer_json_schema, Reader as JsonReader};
+use arrow::record_batch::RecordBatch;
+use parquet::arrow::{ArrowReader, ParquetFileArrowReader};
+use parquet::file::reader::{FileReader, SerializedFileReader};
+use parquet::util::cursor::SliceableCursor;
+
+use datafusion::datasource::datasource::{Statistics, TableProvider};
+use datafusion::error::{DataFusionError, Result};
+use datafusion::logical_plan::Expr;
+use datafusion::physical_plan::ExecutionPlan;
+
+use crate::compression::Compression;
+use crate::execution::{project_schema, spawn_batches, ScanExec};
+use crate::object_store::ObjectStore;
+use crate::union::widen;
+
+/// The format of the files of a `MultiFileTable`
+#[derive(Debug, Clone)]
+pub(crate) enum FileFormat {
+    Parquet,
+    Csv {
+        has_header: bool,
+        delimiter: u8,
+        schema_infer_max_records: usize,
+        // `None` to detect the compression of each file from its extension
+        compression: Option<Compression>,
+    },
+    Json {
+        schema_infer_max_records: usize,
+        compression: Option<Compression>,
+    },
+}
+
+/// opens the file at `path`, from `store` or from the local file system when `store` is `None`
+fn open(store: &Option<Arc<dyn ObjectStore>>, path: &str) -> Result<Box<dyn Read>> {
+    Ok(match store {
+        Some(store) => Box::new(Cursor::new(store.get(path)?)),
+        None => Box::new(BufReader::new(File::open(path)?)),
+    })
+}
+
+/// the Parquet reader of the file at `path`, from `store` or from the local file system when `store` is `None`
+fn parquet_reader(store: &Option<Arc<dyn ObjectStore>>, path: &str) -> Result<Rc<dyn FileReader>> {
+    Ok(match store {
+        Some(store) => Rc::new(SerializedFileReader::new(SliceableCursor::new(
+            store.get(path)?,
+        ))?),
+        None => Rc::new(SerializedFileReader::new(File::open(path)?)?),
+    })
+}
+
+impl FileFormat {
+    /// the schema of the file at `path`
+    pub(crate) fn infer_schema(
+        &self,
+        store: &Option<Arc<dyn ObjectStore>>,
+        path: &str,
+    ) -> Result<Schema> {
+        Ok(match self {
+            FileFormat::Parquet => {
+                ParquetFileArrowReader::new(parquet_reader(store, path)?).get_schema()?
+            }
+            FileFormat::Csv {
+                has_header,
+                delimiter,
+                schema_infer_max_records,
+                compression,
+            } => {
+                // only the lines used for the inference are decompressed
+                let reader = Compression::of(*compression, path).decompress(open(store, path)?)?;
+                let mut reader = BufReader::new(reader);
+                let mut lines = vec![];
+                for _ in 0..*schema_infer_max_records + 1 {
+                    if reader.read_until(b'\n', &mut lines)? == 0 {
+                        break;
+                    }
+                }
+                infer_file_schema(
+                    &mut Cursor::new(lines),
+                    *delimiter,
+                    Some(*schema_infer_max_records),
+                    *has_header,
+                )?
+                .0
+            }
+            FileFormat::Json {
+                schema_infer_max_records,
+                compression,
+            } => {
+                let reader = Compression::of(*compression, path).decompress(open(store, path)?)?;
+                let mut reader = BufReader::new(reader);
+                infer_json_schema(&mut reader, Some(*schema_infer_max_records))?
+                    .as_ref()
+                    .clone()
+            }
+        })
+    }
+
+    /// a reader of the batches of `batch_size` rows of the file at `path`, whose schema is `schema`.
+    /// Parquet files are only read for the columns named in `projection`; other formats are read whole.
+    pub(crate) fn read(
+        &self,
+        store: &Option<Arc<dyn ObjectStore>>,
+        path: &str,
+        schema: SchemaRef,
+        projection: &[String],
+        batch_size: usize,
+    ) -> Result<Box<dyn Iterator<Item = ArrowResult<RecordBatch>>>> {
+        Ok(match self {
+            FileFormat::Parquet => {
+                let reader = parquet_reader(store, path)?;
+                // the leaf columns of the file that are in the projected fields
+                let mut columns = reader
+                    .metadata()
+                    .file_metadata()
+                    .schema_descr()
+                    .columns()
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, column)| projection.contains(&column.path().parts()[0]))
+                    .map(|(i, _)| i)
+                    .collect::<Vec<_>>();
+                if columns.is_empty() {
+                    // a column is still read for the number of rows, and dropped by `align`
+                    columns.push(0);
+                }
+                Box::new(
+                    ParquetFileArrowReader::new(reader)
+                        .get_record_reader_by_columns(columns, batch_size)?,
+                )
+            }
+            FileFormat::Csv {
+                has_header,
+                delimiter,
+                compression,
+                ..
+            } => Box::new(CsvReader::new(
+                Compression::of(*compression, path).decompress(open(store, path)?)?,
+                schema,
+                *has_header,
+                Some(*delimiter),
+                batch_size,
+                None,
+                None,
+            )),
+            FileFormat::Json { compression, .. } => {
+                let mut reader = JsonReader::new(
+                    BufReader::new(
+                        Compression::of(*compression, path).decompress(open(store, path)?)?,
+                    ),
+                    schema,
+                    batch_size,
+                    None,
+                );
+                Box::new(iter::from_fn(move || reader.next().transpose()))
+            }
+        })
+    }
+}
+
+/// the error of a column of the file at `path` whose type can't be widened with that of previous files
+fn incompatible(path: &str, field: &Field, existing: &Field) -> DataFusionError {
+    DataFusionError::Plan(format!(
+        "The column '{}' of '{}' is of type {:?}, which is incompatible with the type {:?} of previous files",
+        field.name(),
+        path,
+        field.data_type(),
+        existing.data_type()
+    ))
+}
+
+/// merges the schemas of the files of a table: fields with the same name are widened to a common type
+/// (e.g. `Int32` and `Int64` to `Int64`), and fields that are not in every file are nullable.
+pub(crate) fn merge_schemas(paths: &[String], schemas: &[Schema]) -> Result<Schema> {
+    let mut fields: Vec<Field> = vec![];
+    for (path, schema) in paths.iter().zip(schemas) {
+        for field in schema.fields() {
+            match fields.iter_mut().find(|f| f.name() == field.name()) {
+                Some(existing) => {
+                    let data_type = widen(existing.data_type(), field.data_type())
+                        .ok_or_else(|| incompatible(path, field, existing))?;
+                    *existing = Field::new(field.name(), data_type, existing.is_nullable());
+                }
+                None => fields.push(field.clone()),
+            }
+        }
+    }
+    let fields = fields
+        .into_iter()
+        .map(|field| {
+            let nullable = schemas.iter().any(|schema| {
+                schema
+                    .field_with_name(field.name())
+                    .map_or(true, |f| f.is_nullable())
+            });
+            Field::new(field.name(), field.data_type().clone(), nullable)
+        })
+        .collect();
+    Ok(Schema::new(fields))
+}
+
+/// aligns `batch` to `schema`, filling the columns that are missing from it with nulls
+/// and casting the others to the type of the table
+pub(crate) fn align(batch: RecordBatch, schema: SchemaRef) -> ArrowResult<RecordBatch> {
+    let columns = schema
+        .fields()
+        .iter()
+        .map(|field| match batch.schema().index_of(field.name()) {
+            Ok(i) if batch.column(i).data_type() == field.data_type() => {
+                Ok(batch.column(i).clone())
+            }
+            Ok(i) if batch.column(i).data_type() != &DataType::Null => {
+                cast(batch.column(i), field.data_type())
+            }
+            _ => Ok(new_null_array(field.data_type(), batch.num_rows())),
+        })
+        .collect::<ArrowResult<Vec<ArrayRef>>>()?;
+    RecordBatch::try_new(schema, columns)
+}
+
+/// A table backed by a list of files of the same format, each of which is a partition.
+/// The schema of the table is the merge of the schemas of the files.
+/// Each file is read when its partition is executed, so that the files of a query are read in parallel.
+pub(crate) struct MultiFileTable {
+    // the store of the files, or `None` for the local file system
+    store: Option<Arc<dyn ObjectStore>>,
+    paths: Vec<String>,
+    // the schema of each file
+    schemas: Vec<SchemaRef>,
+    format: FileFormat,
+    schema: SchemaRef,
+}
+
+impl MultiFileTable {
+    /// Creates a new table from the files at `paths` of `store` (or of the local file system when `None`).
+    /// When `schema` is `None`, the schema of each file is inferred and the schemas are merged;
+    /// otherwise, every file is read with `schema`.
+    pub fn try_new(
+        store: Option<Arc<dyn ObjectStore>>,
+        paths: Vec<String>,
+        format: FileFormat,
+        schema: Option<Schema>,
+    ) -> Result<Self> {
+        if paths.is_empty() {
+            return Err(DataFusionError::Plan(
+                "At least one file is required".to_string(),
+            ));
+        }
+
+        let (schemas, schema) = match schema {
+            Some(schema) => {
+                let schema = Arc::new(schema);
+                (vec![schema.clone(); paths.len()], schema)
+            }
+            None => {
+                let schemas = paths
+                    .iter()
+                    .map(|path| format.infer_schema(&store, path))
+                    .collect::<Result<Vec<_>>>()?;
+                let schema = Arc::new(merge_schemas(&paths, &schemas)?);
+                (schemas.into_iter().map(Arc::new).collect(), schema)
+            }
+        };
+
+        Ok(Self {
+            store,
+            paths,
+            schemas,
+            format,
+            schema,
+        })
+    }
+}
+
+impl TableProvider for MultiFileTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn scan(
+        &self,
+        projection: &Option<Vec<usize>>,
+        batch_size: usize,
+        _filters: &[Expr],
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let (_, schema) = project_schema(&self.schema, projection);
+        let names = schema
+            .fields()
+            .iter()
+            .map(|f| f.name().clone())
+            .collect::<Vec<_>>();
+
+        let store = self.store.clone();
+        let paths = self.paths.clone();
+        let schemas = self.schemas.clone();
+        let format = self.format.clone();
+        Ok(Arc::new(ScanExec::new(
+            "MultiFileExec",
+            schema.clone(),
+            paths.len(),
+            move |partition| {
+                let store = store.clone();
+                let path = paths[partition].clone();
+                let file_schema = schemas[partition].clone();
+                let format = format.clone();
+                let names = names.clone();
+                let schema = schema.clone();
+                Ok(spawn_batches(schema.clone(), move |send| {
+                    for batch in format.read(&store, &path, file_schema, &names, batch_size)? {
+                        if !send(batch.and_then(|batch| align(batch, schema.clone()))) {
+                            break;
+                        }
+                    }
+                    Ok(())
+                }))
+            },
+        )))
+    }
+
+    fn statistics(&self) -> Statistics {
+        Statistics::default()
+    }
+}
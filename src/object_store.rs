@@ -0,0 +1,202 @@
+//! Object stores, from which files of tables registered with a `<scheme>://` path are read.
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use pyo3::prelude::*;
+
+use datafusion::error::{DataFusionError, Result};
+
+use crate::errors::py_error;
+
+/// A store of objects (files) identified by paths
+pub(crate) trait ObjectStore: Send + Sync {
+    /// the contents of the object at `path`
+    fn get(&self, path: &str) -> Result<Vec<u8>>;
+
+    /// the paths of the objects at `path`: `path` itself if it is an object, or the objects under it otherwise
+    fn list(&self, path: &str) -> Result<Vec<String>>;
+}
+
+/// whether the object at `path` is hidden, e.g. `_SUCCESS` or `.crc` files
+fn is_hidden(path: &str) -> bool {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    name.starts_with('_') || name.starts_with('.')
+}
+
+/// The local file system
+pub(crate) struct LocalStore {}
+
+fn list_local(path: &Path, paths: &mut Vec<String>) -> Result<()> {
+    for entry in fs::read_dir(path)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            list_local(&path, paths)?;
+        } else {
+            paths.push(path.to_string_lossy().to_string());
+        }
+    }
+    Ok(())
+}
+
+impl ObjectStore for LocalStore {
+    fn get(&self, path: &str) -> Result<Vec<u8>> {
+        Ok(fs::read(path)?)
+    }
+
+    fn list(&self, path: &str) -> Result<Vec<String>> {
+        if Path::new(path).is_file() {
+            return Ok(vec![path.to_string()]);
+        }
+        let mut paths = vec![];
+        list_local(Path::new(path), &mut paths)?;
+        paths.retain(|path| !is_hidden(path));
+        paths.sort();
+        Ok(paths)
+    }
+}
+
+/// A store that keeps its objects in memory
+#[derive(Default)]
+pub(crate) struct InMemoryStore {
+    objects: Mutex<HashMap<String, Vec<u8>>>,
+}
+
+impl InMemoryStore {
+    pub fn put(&self, path: &str, data: Vec<u8>) {
+        self.objects.lock().unwrap().insert(path.to_string(), data);
+    }
+}
+
+impl ObjectStore for InMemoryStore {
+    fn get(&self, path: &str) -> Result<Vec<u8>> {
+        self.objects
+            .lock()
+            .unwrap()
+            .get(path)
+            .cloned()
+            .ok_or_else(|| DataFusionError::Execution(format!("No object at '{}'", path)))
+    }
+
+    fn list(&self, path: &str) -> Result<Vec<String>> {
+        let objects = self.objects.lock().unwrap();
+        if objects.contains_key(path) {
+            return Ok(vec![path.to_string()]);
+        }
+        let prefix = format!("{}/", path.trim_end_matches('/'));
+        let mut paths = objects
+            .keys()
+            .filter(|key| key.starts_with(&prefix) && !is_hidden(key))
+            .cloned()
+            .collect::<Vec<_>>();
+        paths.sort();
+        Ok(paths)
+    }
+}
+
+/// A store backed by a `pyarrow.fs.FileSystem`, e.g. a `pyarrow.fs.S3FileSystem`,
+/// which also supports S3-compatible services such as MinIO via its `endpoint_override`.
+pub(crate) struct PyArrowStore {
+    filesystem: PyObject,
+}
+
+impl PyArrowStore {
+    pub fn new(filesystem: PyObject) -> Self {
+        Self { filesystem }
+    }
+}
+
+impl ObjectStore for PyArrowStore {
+    fn get(&self, path: &str) -> Result<Vec<u8>> {
+        let gil = pyo3::Python::acquire_gil();
+        let py = gil.python();
+
+        self.filesystem
+            .as_ref(py)
+            .call_method1("open_input_stream", (path,))
+            .and_then(|stream| stream.call_method0("read"))
+            .and_then(|data| data.extract::<Vec<u8>>())
+            .map_err(py_error)
+    }
+
+    fn list(&self, path: &str) -> Result<Vec<String>> {
+        let gil = pyo3::Python::acquire_gil();
+        let py = gil.python();
+
+        let list = || -> PyResult<Vec<String>> {
+            let fs = py.import("pyarrow.fs")?;
+            let filesystem = self.filesystem.as_ref(py);
+
+            let info = filesystem.call_method1("get_file_info", (path,))?;
+            if info.getattr("is_file")?.extract::<bool>()? {
+                return Ok(vec![path.to_string()]);
+            }
+
+            let selector = fs.call1("FileSelector", (path, false, true))?;
+            let mut paths = vec![];
+            for info in filesystem
+                .call_method1("get_file_info", (selector,))?
+                .iter()?
+            {
+                let info = info?;
+                if info.getattr("is_file")?.extract::<bool>()? {
+                    paths.push(info.getattr("path")?.extract::<String>()?);
+                }
+            }
+            Ok(paths)
+        };
+
+        let mut paths = list().map_err(py_error)?;
+        paths.retain(|path| !is_hidden(path));
+        paths.sort();
+        Ok(paths)
+    }
+}
+
+/// An object store that keeps its objects in memory.
+#[pyclass]
+#[derive(Clone)]
+pub(crate) struct InMemoryObjectStore {
+    pub(crate) store: Arc<InMemoryStore>,
+}
+
+#[pymethods]
+impl InMemoryObjectStore {
+    #[new]
+    fn new() -> Self {
+        Self {
+            store: Arc::new(InMemoryStore::default()),
+        }
+    }
+
+    /// Stores `data` at `path`, e.g. "bucket/a.parquet".
+    fn put(&self, path: &str, data: Vec<u8>) {
+        self.store.put(path, data)
+    }
+}
+
+/// An object store that reads from the local file system.
+#[pyclass]
+#[derive(Clone)]
+pub(crate) struct LocalFileSystem {}
+
+#[pymethods]
+impl LocalFileSystem {
+    #[new]
+    fn new() -> Self {
+        Self {}
+    }
+}
+
+/// converts a Python object store into a Rust one: an `InMemoryObjectStore`, a `LocalFileSystem`
+/// or any `pyarrow.fs.FileSystem`.
+pub(crate) fn to_rust_store(store: &PyAny) -> PyResult<Arc<dyn ObjectStore>> {
+    if let Ok(store) = store.extract::<InMemoryObjectStore>() {
+        return Ok(store.store);
+    }
+    if store.extract::<LocalFileSystem>().is_ok() {
+        return Ok(Arc::new(LocalStore {}));
+    }
+    Ok(Arc::new(PyArrowStore::new(store.into())))
+}
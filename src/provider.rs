@@ -0,0 +1,162 @@
+use std::any::Any;
+use std::sync::Arc;
+
+use pyo3::prelude::*;
+
+use arrow::datatypes::SchemaRef;
+use arrow::record_batch::RecordBatch;
+
+use datafusion::datasource::datasource::{Statistics, TableProvider, TableProviderFilterPushDown};
+use datafusion::error::{DataFusionError, Result};
+use datafusion::logical_plan::Expr;
+use datafusion::physical_plan::memory::MemoryExec;
+use datafusion::physical_plan::ExecutionPlan;
+
+use crate::errors::py_error;
+use crate::execution::project_schema;
+use crate::expression::Expression;
+use crate::to_rust::{to_rust_batch, to_rust_schema};
+
+/// A table implemented in Python.
+/// The Python object must implement
+/// * `schema() -> pyarrow.Schema`
+/// * `scan(projection: Optional[List[str]], filters: List[Expression], limit: Optional[int]) -> Iterable[pyarrow.RecordBatch]`
+///
+/// and may implement `supports_filter(filter: Expression) -> bool` to declare the filters
+/// that it fully applies during `scan`. Every other filter is passed to `scan` as a hint
+/// and is re-applied to the result. The columns of the batches that `scan` returns are selected by name,
+/// and must be of the types of the schema.
+pub(crate) struct PyTableProvider {
+    provider: PyObject,
+    schema: SchemaRef,
+}
+
+impl PyTableProvider {
+    pub fn try_new(provider: PyObject, py: Python) -> PyResult<Self> {
+        let schema = provider.as_ref(py).call_method0("schema")?;
+        let schema = Arc::new(to_rust_schema(schema)?);
+        Ok(Self { provider, schema })
+    }
+}
+
+/// the columns of `batch`, returned by the `scan` of a provider, selected by name in the order of `schema`;
+/// errors when a column is missing or of another type
+fn conform(batch: RecordBatch, schema: &SchemaRef) -> Result<RecordBatch> {
+    let columns = schema
+        .fields()
+        .iter()
+        .map(|field| {
+            let column = batch
+                .schema()
+                .index_of(field.name())
+                .ok()
+                .map(|i| batch.column(i).clone())
+                .ok_or_else(|| {
+                    DataFusionError::Execution(format!(
+                        "The batches returned by the scan of a table must have the column '{}', but they have the columns {:?}",
+                        field.name(),
+                        batch
+                            .schema()
+                            .fields()
+                            .iter()
+                            .map(|f| f.name())
+                            .collect::<Vec<_>>()
+                    ))
+                })?;
+            if column.data_type() != field.data_type() {
+                return Err(DataFusionError::Execution(format!(
+                    "The column '{}' returned by the scan of a table is of type {:?}, but the table declares the type {:?}",
+                    field.name(),
+                    column.data_type(),
+                    field.data_type()
+                )));
+            }
+            Ok(column)
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(RecordBatch::try_new(schema.clone(), columns)?)
+}
+
+impl TableProvider for PyTableProvider {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn scan(
+        &self,
+        projection: &Option<Vec<usize>>,
+        _batch_size: usize,
+        filters: &[Expr],
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let gil = pyo3::Python::acquire_gil();
+        let py = gil.python();
+
+        let (_, projected_schema) = project_schema(&self.schema, projection);
+        let names = projection.as_ref().map(|_| {
+            projected_schema
+                .fields()
+                .iter()
+                .map(|f| f.name().clone())
+                .collect::<Vec<_>>()
+        });
+        let filters = filters
+            .iter()
+            .map(|expr| Expression { expr: expr.clone() })
+            .collect::<Vec<_>>();
+        // the engine does not push limits to tables yet
+        let limit: Option<usize> = None;
+
+        let batches = self
+            .provider
+            .as_ref(py)
+            .call_method1("scan", (names, filters, limit))
+            .map_err(py_error)?
+            .iter()
+            .map_err(py_error)?
+            .map(|batch| to_rust_batch(batch?))
+            .collect::<PyResult<Vec<RecordBatch>>>()
+            .map_err(py_error)?
+            .into_iter()
+            .map(|batch| conform(batch, &projected_schema))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Arc::new(MemoryExec::try_new(
+            &[batches],
+            projected_schema,
+            None,
+        )?))
+    }
+
+    fn statistics(&self) -> Statistics {
+        Statistics::default()
+    }
+
+    fn supports_filter_pushdown(&self, filter: &Expr) -> Result<TableProviderFilterPushDown> {
+        let gil = pyo3::Python::acquire_gil();
+        let py = gil.python();
+
+        let provider = self.provider.as_ref(py);
+        if !provider.hasattr("supports_filter").map_err(py_error)? {
+            return Ok(TableProviderFilterPushDown::Inexact);
+        }
+
+        let filter = Expression {
+            expr: filter.clone(),
+        };
+        let exact = provider
+            .call_method1("supports_filter", (filter,))
+            .map_err(py_error)?
+            .extract::<bool>()
+            .map_err(py_error)?;
+        // filters that are not fully applied are still passed to `scan`, as hints
+        Ok(if exact {
+            TableProviderFilterPushDown::Exact
+        } else {
+            TableProviderFilterPushDown::Inexact
+        })
+    }
+}
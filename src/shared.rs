@@ -0,0 +1,166 @@
+//! Tables whose `LogicalPlan` is executed once per query, however many times the query scans them.
+//! They are used by joins that are rewritten into plans that read their inputs more than once.
+use std::any::Any;
+use std::fmt;
+use std::sync::{Arc, Mutex, Weak};
+
+use arrow::datatypes::SchemaRef;
+use arrow::record_batch::RecordBatch;
+use async_trait::async_trait;
+use futures::lock::Mutex as AsyncMutex;
+use futures::stream::TryStreamExt;
+
+use datafusion::datasource::datasource::{Statistics, TableProvider};
+use datafusion::error::{DataFusionError, Result};
+use datafusion::execution::context::ExecutionConfig;
+use datafusion::logical_plan::{Expr, LogicalPlan};
+use datafusion::physical_plan::{ExecutionPlan, Partitioning, SendableRecordBatchStream};
+
+use crate::execution::{
+    check_partition, create_physical_plan, plan_schema, project_batches, project_schema,
+};
+
+// the batches of the plan, once it is executed
+type Cache = AsyncMutex<Option<Vec<RecordBatch>>>;
+
+/// A table with the rows of a `LogicalPlan`. The scans of the table share the batches of the plan
+/// for as long as any of their physical plans exists, i.e. within a query, so that the plan
+/// is executed at most once per query. The batches are kept in memory until the query finishes.
+pub(crate) struct SharedTable {
+    plan: LogicalPlan,
+    schema: SchemaRef,
+    config: ExecutionConfig,
+    cache: Mutex<Weak<Cache>>,
+}
+
+impl SharedTable {
+    pub fn new(plan: LogicalPlan, config: ExecutionConfig) -> Self {
+        let schema = plan_schema(&plan);
+        Self {
+            plan,
+            schema,
+            config,
+            cache: Mutex::new(Weak::new()),
+        }
+    }
+}
+
+impl TableProvider for SharedTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn scan(
+        &self,
+        projection: &Option<Vec<usize>>,
+        _batch_size: usize,
+        _filters: &[Expr],
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        // all columns are cached, since each scan may project different ones
+        let input = create_physical_plan(&self.plan, &None, &self.config)?;
+
+        let cache = {
+            let mut cache = self.cache.lock().unwrap();
+            match cache.upgrade() {
+                Some(shared) => shared,
+                None => {
+                    let shared = Arc::new(AsyncMutex::new(None));
+                    *cache = Arc::downgrade(&shared);
+                    shared
+                }
+            }
+        };
+
+        let (projection, schema) = project_schema(&self.schema, projection);
+
+        Ok(Arc::new(SharedExec {
+            input,
+            cache,
+            projection,
+            schema,
+        }))
+    }
+
+    fn statistics(&self) -> Statistics {
+        Statistics::default()
+    }
+}
+
+/// The physical plan of a scan of a `SharedTable`, with a single partition. The first scan to be
+/// executed executes its input into the cache, from which the others read.
+struct SharedExec {
+    input: Arc<dyn ExecutionPlan>,
+    cache: Arc<Cache>,
+    // the columns of the input that are returned
+    projection: Vec<usize>,
+    schema: SchemaRef,
+}
+
+impl fmt::Debug for SharedExec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SharedExec")
+            .field("input", &self.input)
+            .field("projection", &self.projection)
+            .finish()
+    }
+}
+
+#[async_trait]
+impl ExecutionPlan for SharedExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![self.input.clone()]
+    }
+
+    fn with_new_children(
+        &self,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        match children.as_slice() {
+            [input] => Ok(Arc::new(SharedExec {
+                input: input.clone(),
+                cache: self.cache.clone(),
+                projection: self.projection.clone(),
+                schema: self.schema.clone(),
+            })),
+            _ => Err(DataFusionError::Internal(
+                "SharedExec has a single child".to_string(),
+            )),
+        }
+    }
+
+    async fn execute(&self, partition: usize) -> Result<SendableRecordBatchStream> {
+        check_partition("SharedExec", partition, 1)?;
+
+        let mut cache = self.cache.lock().await;
+        if cache.is_none() {
+            let mut batches = vec![];
+            for i in 0..self.input.output_partitioning().partition_count() {
+                batches.extend(self.input.execute(i).await?.try_collect::<Vec<_>>().await?);
+            }
+            *cache = Some(batches);
+        }
+        let batches = cache.as_ref().unwrap().clone();
+
+        Ok(project_batches(
+            batches.into_iter().map(Ok),
+            self.projection.clone(),
+            self.schema.clone(),
+        ))
+    }
+}
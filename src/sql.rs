@@ -0,0 +1,356 @@
+//! Planning of SQL queries with the joins that the engine's SQL planner does not support (full and cross joins).
+//! Each of these joins is planned as in `DataFrame.join` and registered as a table while the query is planned,
+//! and the query is rewritten to read that table instead of the joined relations.
+use std::sync::{Arc, Mutex};
+
+use pyo3::prelude::*;
+use sqlparser::ast::{
+    BinaryOperator, Expr as SQLExpr, Ident, JoinConstraint, JoinOperator, ObjectName, Query,
+    Select, SetExpr, Statement as SQLStatement, TableFactor, TableWithJoins, With,
+};
+
+use datafusion::error::{DataFusionError as InnerDataFusionError, Result};
+use datafusion::execution::context::{ExecutionContext, ExecutionContextState};
+use datafusion::logical_plan::LogicalPlan;
+use datafusion::sql::parser::{DFParser, Statement};
+
+use crate::context::random_name;
+use crate::dataframe::join_plans;
+use crate::errors;
+use crate::join::column_names;
+use crate::view::ViewTable;
+
+/// plans `query` with the engine's SQL planner
+fn plan_sql(state: &Arc<Mutex<ExecutionContextState>>, query: &str) -> Result<LogicalPlan> {
+    let mut ctx = ExecutionContext::from(state.clone());
+    Ok(ctx.sql(query)?.to_logical_plan())
+}
+
+/// the column that `expr` refers to, as its qualifier (if any) and its name
+fn column(expr: &SQLExpr) -> Option<(Option<&str>, &str)> {
+    match expr {
+        SQLExpr::Identifier(ident) => Some((None, &ident.value)),
+        SQLExpr::CompoundIdentifier(idents) if idents.len() >= 2 => Some((
+            Some(&idents[idents.len() - 2].value),
+            &idents[idents.len() - 1].value,
+        )),
+        SQLExpr::Nested(expr) => column(expr),
+        _ => None,
+    }
+}
+
+/// the name by which the columns of `relation` can be qualified
+fn relation_name(relation: &TableFactor) -> Option<&str> {
+    match relation {
+        TableFactor::Table {
+            alias: Some(alias), ..
+        }
+        | TableFactor::Derived {
+            alias: Some(alias), ..
+        } => Some(&alias.name.value),
+        TableFactor::Table { name, .. } => name.0.last().map(|ident| ident.value.as_str()),
+        _ => None,
+    }
+}
+
+/// appends the pairs of columns that `expr`, equalities joined by `AND`, compares to `pairs`
+fn equalities<'a>(
+    expr: &'a SQLExpr,
+    pairs: &mut Vec<((Option<&'a str>, &'a str), (Option<&'a str>, &'a str))>,
+) -> bool {
+    match expr {
+        SQLExpr::BinaryOp {
+            left,
+            op: BinaryOperator::And,
+            right,
+        } => equalities(left, pairs) && equalities(right, pairs),
+        SQLExpr::BinaryOp {
+            left,
+            op: BinaryOperator::Eq,
+            right,
+        } => match (column(left), column(right)) {
+            (Some(left), Some(right)) => {
+                pairs.push((left, right));
+                true
+            }
+            _ => false,
+        },
+        SQLExpr::Nested(expr) => equalities(expr, pairs),
+        _ => false,
+    }
+}
+
+/// The keys of the full join of `left` and `right` (the relation `relation`) on the condition `on`,
+/// which must compare a column of each side with `=`. Qualified columns are on the side they are
+/// qualified by, and unqualified ones on the side that has them, or as written when both have them.
+fn join_keys(
+    on: &SQLExpr,
+    left: &LogicalPlan,
+    right: &LogicalPlan,
+    relation: &TableFactor,
+) -> PyResult<(Vec<String>, Vec<String>)> {
+    let error = || {
+        errors::DataFusionError::Common(format!(
+            "The condition of a full join must compare columns of both sides with =, joined by AND, but it is {}",
+            on
+        ))
+    };
+    let mut pairs = vec![];
+    if !equalities(on, &mut pairs) {
+        return Err(error().into());
+    }
+
+    let left_names = column_names(left);
+    let right_names = column_names(right);
+    // whether a column is of the right side, when it can be told
+    let is_right = |(qualifier, name): (Option<&str>, &str)| match qualifier {
+        Some(qualifier) => Some(Some(qualifier) == relation_name(relation)),
+        None => match (
+            left_names.iter().any(|n| n == name),
+            right_names.iter().any(|n| n == name),
+        ) {
+            (true, false) => Some(false),
+            (false, true) => Some(true),
+            _ => None,
+        },
+    };
+
+    let mut left_on = vec![];
+    let mut right_on = vec![];
+    for (a, b) in pairs {
+        let (l, r) = if is_right(a) == Some(true) || is_right(b) == Some(false) {
+            (b.1, a.1)
+        } else {
+            (a.1, b.1)
+        };
+        if !left_names.iter().any(|n| n == l) || !right_names.iter().any(|n| n == r) {
+            return Err(error().into());
+        }
+        left_on.push(l.to_string());
+        right_on.push(r.to_string());
+    }
+    Ok((left_on, right_on))
+}
+
+/// the relation that reads the table `name`
+fn table(name: &str) -> TableFactor {
+    TableFactor::Table {
+        name: ObjectName(vec![Ident {
+            value: name.to_string(),
+            quote_style: None,
+        }]),
+        alias: None,
+        args: vec![],
+        with_hints: vec![],
+    }
+}
+
+/// Rewrites the joins of queries into the tables that implement them, which are deregistered when it is dropped.
+struct Rewriter<'a> {
+    state: &'a Arc<Mutex<ExecutionContextState>>,
+    // the tables registered for the rewritten joins
+    registered: Vec<String>,
+    // whether the relations of `FROM a, b` are cross joined
+    implicit: bool,
+    // whether a query has relations of `FROM a, b`
+    found_implicit: bool,
+}
+
+impl<'a> Drop for Rewriter<'a> {
+    fn drop(&mut self) {
+        let mut state = self.state.lock().unwrap();
+        for name in &self.registered {
+            state.datasources.remove(name);
+        }
+    }
+}
+
+impl<'a> Rewriter<'a> {
+    /// Registers the join of the relations `left` and `right` (as written in a `FROM` clause) as a table,
+    /// a full join on `constraint` or a cross join when `None`, and returns its name.
+    fn join(
+        &mut self,
+        left: &TableWithJoins,
+        right: &TableFactor,
+        constraint: Option<&JoinConstraint>,
+        with: Option<&With>,
+    ) -> PyResult<String> {
+        let plan = |from: String| {
+            let query = match with {
+                Some(with) => format!("{} SELECT * FROM {}", with, from),
+                None => format!("SELECT * FROM {}", from),
+            };
+            errors::wrap(plan_sql(self.state, &query))
+        };
+        let left_plan = plan(left.to_string())?;
+        let right_plan = plan(right.to_string())?;
+
+        let (how, left_on, right_on) = match constraint {
+            None => ("cross", vec![], vec![]),
+            Some(JoinConstraint::On(on)) => {
+                let (left_on, right_on) = join_keys(on, &left_plan, &right_plan, right)?;
+                ("full", left_on, right_on)
+            }
+            Some(JoinConstraint::Using(names)) => {
+                let names = names
+                    .iter()
+                    .map(|name| name.value.clone())
+                    .collect::<Vec<_>>();
+                ("full", names.clone(), names)
+            }
+            Some(JoinConstraint::Natural) => {
+                return Err(errors::DataFusionError::Common(
+                    "Natural full joins are not supported".to_string(),
+                )
+                .into())
+            }
+        };
+        let plan = join_plans(
+            self.state,
+            &left_plan,
+            &right_plan,
+            how,
+            left_on.iter().map(|key| key.as_str()).collect(),
+            right_on.iter().map(|key| key.as_str()).collect(),
+            ("_l", "_r"),
+        )?;
+
+        // the name must be a valid unquoted identifier, since the engine looks tables up as written
+        let name = format!("__join_{}", random_name());
+        let config = self.state.lock().unwrap().config.clone();
+        ExecutionContext::from(self.state.clone())
+            .register_table(&name, Box::new(ViewTable::new(plan, config)));
+        self.registered.push(name.clone());
+        Ok(name)
+    }
+
+    fn query(&mut self, query: &mut Query, with: Option<&With>) -> PyResult<()> {
+        if let Some(ctes) = &mut query.with {
+            for cte in &mut ctes.cte_tables {
+                self.query(&mut cte.query, None)?;
+            }
+        }
+        let with = query.with.clone().or_else(|| with.cloned());
+        self.set_expr(&mut query.body, with.as_ref())
+    }
+
+    fn set_expr(&mut self, body: &mut SetExpr, with: Option<&With>) -> PyResult<()> {
+        match body {
+            SetExpr::Select(select) => self.select(select, with),
+            SetExpr::Query(query) => self.query(query, with),
+            SetExpr::SetOperation { left, right, .. } => {
+                self.set_expr(left, with)?;
+                self.set_expr(right, with)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn select(&mut self, select: &mut Select, with: Option<&With>) -> PyResult<()> {
+        for table in &mut select.from {
+            self.table_with_joins(table, with)?;
+        }
+
+        if select.from.len() > 1 {
+            self.found_implicit = true;
+            if self.implicit {
+                let mut tables = select.from.drain(..);
+                let mut left = tables.next().unwrap();
+                for right in tables {
+                    let right = if right.joins.is_empty() {
+                        right.relation
+                    } else {
+                        TableFactor::NestedJoin(Box::new(right))
+                    };
+                    let name = self.join(&left, &right, None, with)?;
+                    left = TableWithJoins {
+                        relation: table(&name),
+                        joins: vec![],
+                    };
+                }
+                select.from = vec![left];
+            }
+        }
+        Ok(())
+    }
+
+    fn table_factor(&mut self, relation: &mut TableFactor, with: Option<&With>) -> PyResult<()> {
+        match relation {
+            TableFactor::Derived { subquery, .. } => self.query(subquery, with),
+            TableFactor::NestedJoin(table) => self.table_with_joins(table, with),
+            _ => Ok(()),
+        }
+    }
+
+    /// rewrites the full and cross joins of `table`, from the first to the last
+    fn table_with_joins(
+        &mut self,
+        table: &mut TableWithJoins,
+        with: Option<&With>,
+    ) -> PyResult<()> {
+        self.table_factor(&mut table.relation, with)?;
+        for join in &mut table.joins {
+            self.table_factor(&mut join.relation, with)?;
+        }
+
+        while let Some(i) = table.joins.iter().position(|join| {
+            matches!(
+                join.join_operator,
+                JoinOperator::FullOuter(_) | JoinOperator::CrossJoin
+            )
+        }) {
+            let rest = table.joins.split_off(i + 1);
+            let join = table.joins.pop().unwrap();
+            let constraint = match &join.join_operator {
+                JoinOperator::FullOuter(constraint) => Some(constraint),
+                _ => None,
+            };
+            let name = self.join(table, &join.relation, constraint, with)?;
+            *table = TableWithJoins {
+                relation: self::table(&name),
+                joins: rest,
+            };
+        }
+        Ok(())
+    }
+}
+
+/// Plans the SQL statement `query` with the context `state`.
+/// Full and cross joins, including the implicit cross joins of `FROM a, b` whose keys are not in `WHERE`,
+/// are planned as in `DataFrame.join`; other statements are planned by the engine.
+pub(crate) fn plan(
+    state: &Arc<Mutex<ExecutionContextState>>,
+    query: &str,
+) -> PyResult<LogicalPlan> {
+    // statements that are not single queries (or that do not parse) are left to the engine
+    let original = match DFParser::parse_sql(query).as_deref() {
+        Ok([Statement::Statement(SQLStatement::Query(original))]) => original.clone(),
+        _ => return errors::wrap(plan_sql(state, query)),
+    };
+
+    let mut rewriter = Rewriter {
+        state,
+        registered: vec![],
+        implicit: false,
+        found_implicit: false,
+    };
+    let mut rewritten = original.clone();
+    rewriter.query(&mut rewritten, None)?;
+    let result = if rewriter.registered.is_empty() {
+        plan_sql(state, query)
+    } else {
+        plan_sql(state, &rewritten.to_string())
+    };
+
+    // the engine only plans the implicit joins whose keys are in `WHERE`: the others are cross joined
+    let result = match result {
+        Err(InnerDataFusionError::NotImplemented(_)) if rewriter.found_implicit => {
+            rewriter.implicit = true;
+            let mut rewritten = original;
+            rewriter.query(&mut rewritten, None)?;
+            plan_sql(state, &rewritten.to_string())
+        }
+        result => result,
+    };
+    errors::wrap(result)
+}
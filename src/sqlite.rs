@@ -0,0 +1,451 @@
+//! Tables of SQLite databases.
+//!
+//! SQLite columns are typed according to the affinity of their declared type:
+//! * `INTEGER` affinity (e.g. `INT`, `BIGINT`) maps to `Int64`
+//! * `TEXT` affinity (e.g. `VARCHAR`, `CLOB`) maps to `Utf8`, as do declared types containing
+//!   `DATE` or `TIME` (which SQLite stores as text) and columns without a declared type
+//! * `BLOB` maps to `Binary`
+//! * declared types containing `BOOL` map to `Boolean`, stored as integers
+//! * `REAL` and `NUMERIC` affinities (e.g. `DOUBLE`, `DECIMAL`) map to `Float64`
+//!
+//! Values that cannot be converted to the type of their column (SQLite does not enforce types) are errors.
+//!
+//! Filters are only pushed down to SQLite on columns whose affinity compares values as the engine does
+//! (`INTEGER` for `Int64`, `TEXT` for `Utf8` and `REAL` or `NUMERIC` for `Float64`). On other columns, e.g.
+//! columns without a declared type, SQLite can compare values of different storage classes (`10` and `'10'`)
+//! differently than the engine compares the converted values, and would drop rows that match.
+use std::any::Any;
+use std::sync::Arc;
+
+use arrow::array::{ArrayRef, BinaryArray, BooleanArray, Float64Array, Int64Array, StringArray};
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use arrow::error::Result as ArrowResult;
+use arrow::record_batch::RecordBatch;
+use rusqlite::types::Value;
+use rusqlite::{Connection, OpenFlags};
+
+use datafusion::datasource::datasource::{Statistics, TableProvider, TableProviderFilterPushDown};
+use datafusion::error::{DataFusionError, Result};
+use datafusion::logical_plan::{Expr, Operator};
+use datafusion::physical_plan::ExecutionPlan;
+use datafusion::scalar::ScalarValue;
+
+use crate::execution::{project_schema, spawn_batches, ScanExec};
+
+fn sqlite_error(e: rusqlite::Error) -> DataFusionError {
+    DataFusionError::Execution(format!("SQLite error: {}", e))
+}
+
+fn open(path: &str) -> Result<Connection> {
+    Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY).map_err(sqlite_error)
+}
+
+/// quotes `name` as an SQLite identifier
+fn quote(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// the names of the tables of the database at `path`
+pub(crate) fn table_names(path: &str) -> Result<Vec<String>> {
+    let connection = open(path)?;
+    let mut statement = connection
+        .prepare(
+            "SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name",
+        )
+        .map_err(sqlite_error)?;
+    let names = statement
+        .query_map(rusqlite::NO_PARAMS, |row| row.get(0))
+        .map_err(sqlite_error)?
+        .collect::<rusqlite::Result<Vec<String>>>()
+        .map_err(sqlite_error)?;
+    Ok(names)
+}
+
+/// The affinity of a column, see https://www.sqlite.org/datatype3.html#determination_of_column_affinity
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Affinity {
+    Integer,
+    Text,
+    Blob,
+    Real,
+    Numeric,
+}
+
+/// the affinity of a column whose declared SQLite type is `declared_type`
+fn to_affinity(declared_type: &str) -> Affinity {
+    let declared_type = declared_type.to_uppercase();
+    let contains = |s: &str| declared_type.contains(s);
+    if contains("INT") {
+        Affinity::Integer
+    } else if contains("CHAR") || contains("CLOB") || contains("TEXT") {
+        Affinity::Text
+    } else if contains("BLOB") || declared_type.is_empty() {
+        Affinity::Blob
+    } else if contains("REAL") || contains("FLOA") || contains("DOUB") {
+        Affinity::Real
+    } else {
+        Affinity::Numeric
+    }
+}
+
+/// whether SQLite compares the values of a column with `affinity` to literals as the engine
+/// compares them once converted to `data_type`
+fn compares_as_engine(affinity: Affinity, data_type: &DataType) -> bool {
+    matches!(
+        (affinity, data_type),
+        (Affinity::Integer, DataType::Int64)
+            | (Affinity::Text, DataType::Utf8)
+            | (Affinity::Real, DataType::Float64)
+            | (Affinity::Numeric, DataType::Float64)
+    )
+}
+
+/// the Arrow type of a column whose declared SQLite type is `declared_type`
+fn to_data_type(declared_type: &str) -> DataType {
+    let declared_type = declared_type.to_uppercase();
+    let contains = |s: &str| declared_type.contains(s);
+    if contains("BOOL") {
+        DataType::Boolean
+    } else if contains("INT") {
+        DataType::Int64
+    } else if contains("CHAR")
+        || contains("CLOB")
+        || contains("TEXT")
+        || contains("DATE")
+        || contains("TIME")
+        || declared_type.is_empty()
+    {
+        DataType::Utf8
+    } else if contains("BLOB") {
+        DataType::Binary
+    } else {
+        DataType::Float64
+    }
+}
+
+/// converts the `values` of the column `field` into an array
+fn to_array(field: &Field, values: &[Value]) -> Result<ArrayRef> {
+    let error = |value: &Value| {
+        DataFusionError::Execution(format!(
+            "The value {:?} of the column '{}' cannot be read as {:?}",
+            value,
+            field.name(),
+            field.data_type()
+        ))
+    };
+    Ok(match field.data_type() {
+        DataType::Boolean => Arc::new(BooleanArray::from(
+            values
+                .iter()
+                .map(|value| match value {
+                    Value::Null => Ok(None),
+                    Value::Integer(v) => Ok(Some(*v != 0)),
+                    other => Err(error(other)),
+                })
+                .collect::<Result<Vec<_>>>()?,
+        )),
+        DataType::Int64 => Arc::new(Int64Array::from(
+            values
+                .iter()
+                .map(|value| match value {
+                    Value::Null => Ok(None),
+                    Value::Integer(v) => Ok(Some(*v)),
+                    other => Err(error(other)),
+                })
+                .collect::<Result<Vec<_>>>()?,
+        )),
+        DataType::Float64 => Arc::new(Float64Array::from(
+            values
+                .iter()
+                .map(|value| match value {
+                    Value::Null => Ok(None),
+                    Value::Integer(v) => Ok(Some(*v as f64)),
+                    Value::Real(v) => Ok(Some(*v)),
+                    other => Err(error(other)),
+                })
+                .collect::<Result<Vec<_>>>()?,
+        )),
+        DataType::Utf8 => {
+            let values = values
+                .iter()
+                .map(|value| match value {
+                    Value::Null => Ok(None),
+                    Value::Integer(v) => Ok(Some(v.to_string())),
+                    Value::Real(v) => Ok(Some(v.to_string())),
+                    Value::Text(v) => Ok(Some(v.clone())),
+                    other => Err(error(other)),
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Arc::new(StringArray::from(
+                values.iter().map(|v| v.as_deref()).collect::<Vec<_>>(),
+            ))
+        }
+        DataType::Binary => Arc::new(BinaryArray::from(
+            values
+                .iter()
+                .map(|value| match value {
+                    Value::Null => Ok(None),
+                    Value::Blob(v) => Ok(Some(v.as_slice())),
+                    Value::Text(v) => Ok(Some(v.as_bytes())),
+                    other => Err(error(other)),
+                })
+                .collect::<Result<Vec<_>>>()?,
+        )),
+        other => {
+            return Err(DataFusionError::NotImplemented(format!(
+                "The type {:?} is not supported",
+                other
+            )))
+        }
+    })
+}
+
+/// converts `value` into an SQLite value that compares to columns of type `data_type` as
+/// the engine would, or `None` if it can't
+fn to_value(value: &ScalarValue, data_type: &DataType) -> Option<Value> {
+    Some(match (value, data_type) {
+        (ScalarValue::Boolean(Some(v)), DataType::Boolean) => Value::Integer(*v as i64),
+        (ScalarValue::Int8(Some(v)), DataType::Int64) => Value::Integer(*v as i64),
+        (ScalarValue::Int16(Some(v)), DataType::Int64) => Value::Integer(*v as i64),
+        (ScalarValue::Int32(Some(v)), DataType::Int64) => Value::Integer(*v as i64),
+        (ScalarValue::Int64(Some(v)), DataType::Int64) => Value::Integer(*v),
+        (ScalarValue::Int8(Some(v)), DataType::Float64) => Value::Real(*v as f64),
+        (ScalarValue::Int16(Some(v)), DataType::Float64) => Value::Real(*v as f64),
+        (ScalarValue::Int32(Some(v)), DataType::Float64) => Value::Real(*v as f64),
+        (ScalarValue::Int64(Some(v)), DataType::Float64) => Value::Real(*v as f64),
+        (ScalarValue::Float32(Some(v)), DataType::Float64) => Value::Real(*v as f64),
+        (ScalarValue::Float64(Some(v)), DataType::Float64) => Value::Real(*v),
+        (ScalarValue::Utf8(Some(v)), DataType::Utf8) => Value::Text(v.clone()),
+        _ => return None,
+    })
+}
+
+/// A table of an SQLite database. The table is read when its scan is executed, and its rows are
+/// streamed in batches: the projection and the filters that can be expressed in SQL are part of the
+/// query sent to SQLite.
+pub(crate) struct SqliteTable {
+    path: String,
+    table: String,
+    schema: SchemaRef,
+    // whether filters on each column can be pushed down to SQLite, see `compares_as_engine`
+    pushdown: Vec<bool>,
+}
+
+impl SqliteTable {
+    /// Creates a new table from the table `table` of the database at `path`.
+    pub fn try_new(path: &str, table: &str) -> Result<Self> {
+        let connection = open(path)?;
+        let mut statement = connection
+            .prepare(&format!("PRAGMA table_info({})", quote(table)))
+            .map_err(sqlite_error)?;
+        // the columns of `table_info` are `cid`, `name`, `type`, `notnull`, `dflt_value` and `pk`
+        let (fields, pushdown): (Vec<_>, Vec<_>) = statement
+            .query_map(rusqlite::NO_PARAMS, |row| {
+                let name: String = row.get(1)?;
+                let declared_type: String = row.get(2)?;
+                let not_null: bool = row.get(3)?;
+                let data_type = to_data_type(&declared_type);
+                let pushdown = compares_as_engine(to_affinity(&declared_type), &data_type);
+                Ok((Field::new(&name, data_type, !not_null), pushdown))
+            })
+            .map_err(sqlite_error)?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .map_err(sqlite_error)?
+            .into_iter()
+            .unzip();
+        if fields.is_empty() {
+            return Err(DataFusionError::Plan(format!(
+                "The database '{}' has no table '{}'",
+                path, table
+            )));
+        }
+
+        Ok(Self {
+            path: path.to_string(),
+            table: table.to_string(),
+            schema: Arc::new(Schema::new(fields)),
+            pushdown,
+        })
+    }
+
+    /// converts `expr` into an SQL condition whose parameters are appended to `params`,
+    /// or `None` if it can't be expressed in SQL
+    fn to_sql(&self, expr: &Expr, params: &mut Vec<Value>) -> Option<String> {
+        match expr {
+            Expr::BinaryExpr { left, op, right } => match op {
+                Operator::And | Operator::Or => {
+                    let left = self.to_sql(left, params)?;
+                    let right = self.to_sql(right, params)?;
+                    let op = if *op == Operator::And { "AND" } else { "OR" };
+                    Some(format!("({} {} {})", left, op, right))
+                }
+                Operator::Eq
+                | Operator::NotEq
+                | Operator::Lt
+                | Operator::LtEq
+                | Operator::Gt
+                | Operator::GtEq => {
+                    // only comparisons between a column and a literal, e.g. `a > 1` or `1 < a`
+                    let (name, value, op) = match (left.as_ref(), right.as_ref()) {
+                        (Expr::Column(name), Expr::Literal(value)) => (name, value, op.clone()),
+                        (Expr::Literal(value), Expr::Column(name)) => {
+                            let op = match op {
+                                Operator::Lt => Operator::Gt,
+                                Operator::LtEq => Operator::GtEq,
+                                Operator::Gt => Operator::Lt,
+                                Operator::GtEq => Operator::LtEq,
+                                op => op.clone(),
+                            };
+                            (name, value, op)
+                        }
+                        _ => return None,
+                    };
+                    let i = self.schema.index_of(name).ok()?;
+                    if !self.pushdown[i] {
+                        return None;
+                    }
+                    params.push(to_value(value, self.schema.field(i).data_type())?);
+                    let op = match op {
+                        Operator::Eq => "=",
+                        Operator::NotEq => "<>",
+                        Operator::Lt => "<",
+                        Operator::LtEq => "<=",
+                        Operator::Gt => ">",
+                        _ => ">=",
+                    };
+                    // text is compared byte by byte, as the engine does, regardless of the column's collation
+                    Some(format!("{} {} ? COLLATE BINARY", quote(name), op))
+                }
+                _ => None,
+            },
+            Expr::Not(expr) => Some(format!("(NOT {})", self.to_sql(expr, params)?)),
+            Expr::IsNull(expr) => match expr.as_ref() {
+                Expr::Column(name) => Some(format!("{} IS NULL", quote(name))),
+                _ => None,
+            },
+            Expr::IsNotNull(expr) => match expr.as_ref() {
+                Expr::Column(name) => Some(format!("{} IS NOT NULL", quote(name))),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// the SQL query of the columns `fields` of the rows that satisfy `filters` (those that can be
+    /// expressed in SQL), and its parameters
+    fn query(&self, fields: &[Field], filters: &[Expr]) -> (String, Vec<Value>) {
+        let columns = fields
+            .iter()
+            .map(|f| quote(f.name()))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let mut query = format!("SELECT {} FROM {}", columns, quote(&self.table));
+        let mut params = vec![];
+        let conditions = filters
+            .iter()
+            .filter_map(|filter| {
+                let mut filter_params = vec![];
+                let condition = self.to_sql(filter, &mut filter_params)?;
+                params.extend(filter_params);
+                Some(condition)
+            })
+            .collect::<Vec<_>>();
+        if !conditions.is_empty() {
+            query = format!("{} WHERE {}", query, conditions.join(" AND "));
+        }
+        (query, params)
+    }
+}
+
+/// runs `query` with `params` on the database at `path` and sends its rows, whose columns are those of
+/// `schema`, in batches of `batch_size` rows until `send` returns false
+fn read(
+    path: &str,
+    query: &str,
+    params: Vec<Value>,
+    schema: &SchemaRef,
+    batch_size: usize,
+    send: &mut dyn FnMut(ArrowResult<RecordBatch>) -> bool,
+) -> Result<()> {
+    let connection = open(path)?;
+    let mut statement = connection.prepare(query).map_err(sqlite_error)?;
+    let mut rows = statement.query(params).map_err(sqlite_error)?;
+
+    let fields = schema.fields();
+    let to_batch = |columns: &[Vec<Value>]| {
+        let arrays = fields
+            .iter()
+            .zip(columns)
+            .map(|(field, values)| to_array(field, values))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(RecordBatch::try_new(schema.clone(), arrays)?)
+    };
+
+    // rows are converted into a batch every `batch_size` rows
+    let mut columns = vec![Vec::with_capacity(batch_size); fields.len()];
+    let mut length = 0;
+    while let Some(row) = rows.next().map_err(sqlite_error)? {
+        for (i, column) in columns.iter_mut().enumerate() {
+            column.push(row.get::<_, Value>(i).map_err(sqlite_error)?);
+        }
+        length += 1;
+        if length == batch_size {
+            if !send(Ok(to_batch(&columns)?)) {
+                return Ok(());
+            }
+            columns.iter_mut().for_each(|column| column.clear());
+            length = 0;
+        }
+    }
+    if length > 0 {
+        send(Ok(to_batch(&columns)?));
+    }
+    Ok(())
+}
+
+impl TableProvider for SqliteTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn scan(
+        &self,
+        projection: &Option<Vec<usize>>,
+        batch_size: usize,
+        filters: &[Expr],
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let (_, schema) = project_schema(&self.schema, projection);
+        let (query, params) = self.query(schema.fields(), filters);
+        let path = self.path.clone();
+
+        // the database is only queried when the scan is executed, from a thread of its own
+        Ok(Arc::new(ScanExec::new(
+            "SqliteExec",
+            schema.clone(),
+            1,
+            move |_| {
+                let (path, query, params) = (path.clone(), query.clone(), params.clone());
+                let schema = schema.clone();
+                Ok(spawn_batches(schema.clone(), move |send| {
+                    read(&path, &query, params, &schema, batch_size, send)
+                }))
+            },
+        )))
+    }
+
+    fn statistics(&self) -> Statistics {
+        Statistics::default()
+    }
+
+    fn supports_filter_pushdown(&self, filter: &Expr) -> Result<TableProviderFilterPushDown> {
+        Ok(match self.to_sql(filter, &mut vec![]) {
+            // the filter is only a hint: the engine re-applies it
+            Some(_) => TableProviderFilterPushDown::Inexact,
+            None => TableProviderFilterPushDown::Unsupported,
+        })
+    }
+}
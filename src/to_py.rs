@@ -1,10 +1,13 @@
 use pyo3::prelude::*;
 use pyo3::{libc::uintptr_t, PyErr};
 
+use std::collections::HashMap;
 use std::convert::From;
 
 use arrow::array::ArrayRef;
+use arrow::datatypes::{DataType, Field, Schema, TimeUnit};
 use arrow::record_batch::RecordBatch;
+use datafusion::scalar::ScalarValue;
 
 use crate::errors;
 
@@ -22,6 +25,136 @@ pub fn to_py_array(array: &ArrayRef, py: Python) -> PyResult<PyObject> {
     Ok(array.to_object(py))
 }
 
+/// converts a Rust Scalar into a Python object
+pub fn to_py_scalar(scalar: &ScalarValue, py: Python) -> PyResult<PyObject> {
+    Ok(match scalar {
+        ScalarValue::Boolean(v) => v.to_object(py),
+        ScalarValue::Int8(v) => v.to_object(py),
+        ScalarValue::Int16(v) => v.to_object(py),
+        ScalarValue::Int32(v) => v.to_object(py),
+        ScalarValue::Int64(v) => v.to_object(py),
+        ScalarValue::UInt8(v) => v.to_object(py),
+        ScalarValue::UInt16(v) => v.to_object(py),
+        ScalarValue::UInt32(v) => v.to_object(py),
+        ScalarValue::UInt64(v) => v.to_object(py),
+        ScalarValue::Float32(v) => v.to_object(py),
+        ScalarValue::Float64(v) => v.to_object(py),
+        ScalarValue::Utf8(v) => v.to_object(py),
+        ScalarValue::LargeUtf8(v) => v.to_object(py),
+        other => {
+            return Err(errors::DataFusionError::Common(format!(
+                "Scalar \"{:?}\" not yet implemented",
+                other
+            ))
+            .into())
+        }
+    })
+}
+
+fn time_unit(unit: &TimeUnit) -> &'static str {
+    match unit {
+        TimeUnit::Second => "s",
+        TimeUnit::Millisecond => "ms",
+        TimeUnit::Microsecond => "us",
+        TimeUnit::Nanosecond => "ns",
+    }
+}
+
+/// converts a Rust DataType into a pyarrow DataType
+fn to_py_data_type(data_type: &DataType, pyarrow: &PyModule) -> PyResult<PyObject> {
+    let py = pyarrow.py();
+    let data_type = match data_type {
+        DataType::Null => pyarrow.call0("null")?,
+        DataType::Boolean => pyarrow.call0("bool_")?,
+        DataType::Int8 => pyarrow.call0("int8")?,
+        DataType::Int16 => pyarrow.call0("int16")?,
+        DataType::Int32 => pyarrow.call0("int32")?,
+        DataType::Int64 => pyarrow.call0("int64")?,
+        DataType::UInt8 => pyarrow.call0("uint8")?,
+        DataType::UInt16 => pyarrow.call0("uint16")?,
+        DataType::UInt32 => pyarrow.call0("uint32")?,
+        DataType::UInt64 => pyarrow.call0("uint64")?,
+        DataType::Float16 => pyarrow.call0("float16")?,
+        DataType::Float32 => pyarrow.call0("float32")?,
+        DataType::Float64 => pyarrow.call0("float64")?,
+        DataType::Utf8 => pyarrow.call0("utf8")?,
+        DataType::LargeUtf8 => pyarrow.call0("large_utf8")?,
+        DataType::Binary => pyarrow.call0("binary")?,
+        DataType::LargeBinary => pyarrow.call0("large_binary")?,
+        DataType::FixedSizeBinary(size) => pyarrow.call1("binary", (*size,))?,
+        DataType::Date32(_) => pyarrow.call0("date32")?,
+        DataType::Date64(_) => pyarrow.call0("date64")?,
+        DataType::Time32(unit) => pyarrow.call1("time32", (time_unit(unit),))?,
+        DataType::Time64(unit) => pyarrow.call1("time64", (time_unit(unit),))?,
+        DataType::Duration(unit) => pyarrow.call1("duration", (time_unit(unit),))?,
+        DataType::Timestamp(unit, tz) => {
+            pyarrow.call1("timestamp", (time_unit(unit), tz.clone()))?
+        }
+        DataType::Decimal(precision, scale) => pyarrow.call1("decimal128", (*precision, *scale))?,
+        DataType::List(field) => pyarrow.call1("list_", (to_py_field(field, pyarrow)?,))?,
+        DataType::LargeList(field) => {
+            pyarrow.call1("large_list", (to_py_field(field, pyarrow)?,))?
+        }
+        DataType::Struct(fields) => pyarrow.call1(
+            "struct",
+            (fields
+                .iter()
+                .map(|field| to_py_field(field, pyarrow))
+                .collect::<PyResult<Vec<_>>>()?,),
+        )?,
+        DataType::Dictionary(key, value) => pyarrow.call1(
+            "dictionary",
+            (
+                to_py_data_type(key, pyarrow)?,
+                to_py_data_type(value, pyarrow)?,
+            ),
+        )?,
+        other => {
+            return Err(errors::DataFusionError::Common(format!(
+                "Type \"{:?}\" not yet implemented",
+                other
+            ))
+            .into())
+        }
+    };
+    Ok(data_type.to_object(py))
+}
+
+fn to_py_field(field: &Field, pyarrow: &PyModule) -> PyResult<PyObject> {
+    let field = pyarrow.call1(
+        "field",
+        (
+            field.name(),
+            to_py_data_type(field.data_type(), pyarrow)?,
+            field.is_nullable(),
+            field
+                .metadata()
+                .as_ref()
+                .map(|metadata| metadata.clone().into_iter().collect::<HashMap<_, _>>()),
+        ),
+    )?;
+    Ok(field.to_object(pyarrow.py()))
+}
+
+/// converts a Rust Schema into a pyarrow Schema
+pub fn to_py_schema(schema: &Schema, py: Python) -> PyResult<PyObject> {
+    let pyarrow = PyModule::import(py, "pyarrow")?;
+
+    let fields = schema
+        .fields()
+        .iter()
+        .map(|field| to_py_field(field, pyarrow))
+        .collect::<PyResult<Vec<_>>>()?;
+    let metadata = if schema.metadata().is_empty() {
+        None
+    } else {
+        Some(schema.metadata().clone())
+    };
+
+    let schema = pyarrow.call1("schema", (fields, metadata))?;
+    Ok(schema.to_object(py))
+}
+
 fn to_py_batch<'a>(
     batch: &RecordBatch,
     py: Python,
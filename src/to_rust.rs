@@ -1,3 +1,5 @@
+use std::collections::{BTreeMap, HashMap};
+use std::iter::FromIterator;
 use std::sync::Arc;
 
 use arrow::{
@@ -30,8 +32,24 @@ pub fn to_rust(ob: &PyAny) -> PyResult<ArrayRef> {
     Ok(array)
 }
 
-pub fn to_rust_batch(batch: &PyAny) -> PyResult<RecordBatch> {
-    let schema = batch.getattr("schema")?;
+/// converts pyarrow metadata, a dict of bytes or `None`, into Rust metadata
+fn to_rust_metadata<M: FromIterator<(String, String)>>(metadata: &PyAny) -> PyResult<M> {
+    let metadata = metadata
+        .extract::<Option<HashMap<&[u8], &[u8]>>>()?
+        .unwrap_or_default();
+    Ok(metadata
+        .into_iter()
+        .map(|(key, value)| {
+            (
+                String::from_utf8_lossy(key).to_string(),
+                String::from_utf8_lossy(value).to_string(),
+            )
+        })
+        .collect())
+}
+
+/// converts a pyarrow Schema into a Rust Schema
+pub fn to_rust_schema(schema: &PyAny) -> PyResult<Schema> {
     let names = schema.getattr("names")?.extract::<Vec<String>>()?;
 
     let fields = names
@@ -42,13 +60,26 @@ pub fn to_rust_batch(batch: &PyAny) -> PyResult<RecordBatch> {
             let nullable = field.getattr("nullable")?.extract::<bool>()?;
             let py_data_type = field.getattr("type")?;
             let data_type = py_data_type.extract::<PyDataType>()?.data_type;
-            Ok(Field::new(name, data_type, nullable))
+            let metadata: BTreeMap<_, _> = to_rust_metadata(field.getattr("metadata")?)?;
+
+            let mut field = Field::new(name, data_type, nullable);
+            if !metadata.is_empty() {
+                field.set_metadata(Some(metadata));
+            }
+            Ok(field)
         })
         .collect::<PyResult<_>>()?;
 
-    let schema = Arc::new(Schema::new(fields));
+    Ok(Schema::new_with_metadata(
+        fields,
+        to_rust_metadata(schema.getattr("metadata")?)?,
+    ))
+}
+
+pub fn to_rust_batch(batch: &PyAny) -> PyResult<RecordBatch> {
+    let schema = Arc::new(to_rust_schema(batch.getattr("schema")?)?);
 
-    let arrays = (0..names.len())
+    let arrays = (0..schema.fields().len())
         .map(|i| {
             let array = batch.call_method1("column", (i,))?;
             to_rust(array)
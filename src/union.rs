@@ -0,0 +1,290 @@
+//! Tables with the rows of two `LogicalPlan`s, for `DataFrame.union`.
+use std::any::Any;
+use std::sync::Arc;
+
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use arrow::record_batch::RecordBatch;
+use async_trait::async_trait;
+
+use datafusion::datasource::datasource::{Statistics, TableProvider};
+use datafusion::error::{DataFusionError, Result};
+use datafusion::execution::context::ExecutionConfig;
+use datafusion::logical_plan::{col, Expr, LogicalPlan, LogicalPlanBuilder};
+use datafusion::physical_plan::{ExecutionPlan, Partitioning, SendableRecordBatchStream};
+
+use crate::execution::{
+    check_partition, create_physical_plan, plan_schema, project_schema, MapStream,
+};
+
+/// the type to which columns of types `left` and `right` are widened, or `None` if there is none
+pub(crate) fn widen(left: &DataType, right: &DataType) -> Option<DataType> {
+    use DataType::*;
+
+    // the bit width of signed and unsigned integers
+    let signed = |t: &DataType| match t {
+        Int8 => Some(8),
+        Int16 => Some(16),
+        Int32 => Some(32),
+        Int64 => Some(64),
+        _ => None,
+    };
+    let unsigned = |t: &DataType| match t {
+        UInt8 => Some(8),
+        UInt16 => Some(16),
+        UInt32 => Some(32),
+        UInt64 => Some(64),
+        _ => None,
+    };
+    let is_float = |t: &DataType| matches!(t, Float32 | Float64);
+    let is_integer = |t: &DataType| signed(t).is_some() || unsigned(t).is_some();
+
+    Some(match (left, right) {
+        (left, right) if left == right => left.clone(),
+        (Null, other) | (other, Null) => other.clone(),
+        (Utf8, LargeUtf8) | (LargeUtf8, Utf8) => LargeUtf8,
+        (left, right) if signed(left).is_some() && signed(right).is_some() => {
+            if signed(left) > signed(right) {
+                left.clone()
+            } else {
+                right.clone()
+            }
+        }
+        (left, right) if unsigned(left).is_some() && unsigned(right).is_some() => {
+            if unsigned(left) > unsigned(right) {
+                left.clone()
+            } else {
+                right.clone()
+            }
+        }
+        (left, right) if is_float(left) || is_float(right) => {
+            if (is_float(left) || is_integer(left)) && (is_float(right) || is_integer(right)) {
+                Float64
+            } else {
+                return None;
+            }
+        }
+        (left, right) if is_integer(left) && is_integer(right) => {
+            // a signed and an unsigned integer: the smallest signed integer wider than both
+            let bits = |t: &DataType| signed(t).or_else(|| unsigned(t).map(|bits| bits * 2));
+            match bits(left)?.max(bits(right)?) {
+                16 => Int16,
+                32 => Int32,
+                64 => Int64,
+                _ => return None,
+            }
+        }
+        _ => return None,
+    })
+}
+
+/// A table with the rows of two plans, whose columns are aligned by position or by name.
+/// The plans are executed when a query that uses the table is executed (see `UnionExec`).
+pub(crate) struct UnionTable {
+    left: LogicalPlan,
+    right: LogicalPlan,
+    schema: SchemaRef,
+    config: ExecutionConfig,
+}
+
+impl UnionTable {
+    /// Creates a new table with the rows of `left` followed by the rows of `right`.
+    /// When `by_name` is false, columns are aligned by position and must be of the same type;
+    /// the names are those of `left`. When `by_name` is true, both plans must have the same column names:
+    /// the columns of `right` are reordered as in `left` and the columns of different types are
+    /// widened to a common type (e.g. `Int32` and `Int64` to `Int64`).
+    pub fn try_new(
+        left: LogicalPlan,
+        right: LogicalPlan,
+        by_name: bool,
+        config: ExecutionConfig,
+    ) -> Result<Self> {
+        let left_fields = plan_schema(&left).fields().clone();
+        let right_fields = plan_schema(&right).fields().clone();
+
+        // the field of `right` aligned to each field of `left`
+        let pairs = if by_name {
+            let missing = |fields: &[Field], other: &[Field]| {
+                fields
+                    .iter()
+                    .filter(|f| !other.iter().any(|o| o.name() == f.name()))
+                    .map(|f| f.name().clone())
+                    .collect::<Vec<_>>()
+            };
+            let left_only = missing(&left_fields, &right_fields);
+            let right_only = missing(&right_fields, &left_fields);
+            if !left_only.is_empty() || !right_only.is_empty() {
+                return Err(DataFusionError::Plan(format!(
+                    "The columns {:?} are only on the left and the columns {:?} are only on the right",
+                    left_only, right_only
+                )));
+            }
+            left_fields
+                .iter()
+                .map(|l| {
+                    let r = right_fields.iter().find(|r| r.name() == l.name()).unwrap();
+                    (l, r)
+                })
+                .collect::<Vec<_>>()
+        } else {
+            if left_fields.len() != right_fields.len() {
+                return Err(DataFusionError::Plan(format!(
+                    "The left side has {} columns but the right side has {}",
+                    left_fields.len(),
+                    right_fields.len()
+                )));
+            }
+            left_fields.iter().zip(right_fields.iter()).collect()
+        };
+
+        let fields = pairs
+            .iter()
+            .enumerate()
+            .map(|(i, (l, r))| {
+                let data_type = if by_name {
+                    widen(l.data_type(), r.data_type())
+                } else if l.data_type() == r.data_type() {
+                    Some(l.data_type().clone())
+                } else {
+                    None
+                };
+                let data_type = data_type.ok_or_else(|| {
+                    DataFusionError::Plan(format!(
+                        "The column {} is of type {:?} ('{}') on the left and {:?} ('{}') on the right",
+                        i,
+                        l.data_type(),
+                        l.name(),
+                        r.data_type(),
+                        r.name()
+                    ))
+                })?;
+                Ok(Field::new(
+                    l.name(),
+                    data_type,
+                    l.is_nullable() || r.is_nullable(),
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        // each plan is projected to the columns of the union, cast to their types and named as in `left`
+        let project = |plan: &LogicalPlan, names: Vec<&String>| -> Result<LogicalPlan> {
+            let expressions = names
+                .into_iter()
+                .zip(fields.iter())
+                .map(|(name, field)| {
+                    Expr::Cast {
+                        expr: Box::new(col(name)),
+                        data_type: field.data_type().clone(),
+                    }
+                    .alias(field.name())
+                })
+                .collect();
+            LogicalPlanBuilder::from(plan).project(expressions)?.build()
+        };
+        let left = project(&left, pairs.iter().map(|(l, _)| l.name()).collect())?;
+        let right = project(&right, pairs.iter().map(|(_, r)| r.name()).collect())?;
+
+        Ok(Self {
+            left,
+            right,
+            schema: Arc::new(Schema::new(fields)),
+            config,
+        })
+    }
+}
+
+impl TableProvider for UnionTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn scan(
+        &self,
+        projection: &Option<Vec<usize>>,
+        _batch_size: usize,
+        _filters: &[Expr],
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let (_, schema) = project_schema(&self.schema, projection);
+
+        Ok(Arc::new(UnionExec {
+            children: vec![
+                create_physical_plan(&self.left, projection, &self.config)?,
+                create_physical_plan(&self.right, projection, &self.config)?,
+            ],
+            schema,
+        }))
+    }
+
+    fn statistics(&self) -> Statistics {
+        Statistics::default()
+    }
+}
+
+/// The physical plan of a `UnionTable`: the partitions of its children, one after the other.
+#[derive(Debug)]
+struct UnionExec {
+    children: Vec<Arc<dyn ExecutionPlan>>,
+    schema: SchemaRef,
+}
+
+#[async_trait]
+impl ExecutionPlan for UnionExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(
+            self.children
+                .iter()
+                .map(|child| child.output_partitioning().partition_count())
+                .sum(),
+        )
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        self.children.clone()
+    }
+
+    fn with_new_children(
+        &self,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        Ok(Arc::new(UnionExec {
+            children,
+            schema: self.schema.clone(),
+        }))
+    }
+
+    async fn execute(&self, partition: usize) -> Result<SendableRecordBatchStream> {
+        check_partition(
+            "UnionExec",
+            partition,
+            self.output_partitioning().partition_count(),
+        )?;
+
+        let mut remaining = partition;
+        for child in &self.children {
+            let count = child.output_partitioning().partition_count();
+            if remaining < count {
+                let input = child.execute(remaining).await?;
+                // the batches take the nullability of the union's schema
+                let schema = self.schema.clone();
+                return Ok(Box::pin(MapStream::new(
+                    self.schema.clone(),
+                    input,
+                    move |batch| RecordBatch::try_new(schema.clone(), batch.columns().to_vec()),
+                )));
+            }
+            remaining -= count;
+        }
+        unreachable!()
+    }
+}
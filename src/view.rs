@@ -0,0 +1,54 @@
+use std::any::Any;
+use std::sync::Arc;
+
+use arrow::datatypes::SchemaRef;
+
+use datafusion::datasource::datasource::{Statistics, TableProvider};
+use datafusion::error::Result;
+use datafusion::execution::context::ExecutionConfig;
+use datafusion::logical_plan::{Expr, LogicalPlan};
+use datafusion::physical_plan::ExecutionPlan;
+
+use crate::execution::{create_physical_plan, plan_schema};
+
+/// A table whose contents are defined by a `LogicalPlan`.
+/// The plan is only optimized and executed when a query that uses the table is executed.
+pub(crate) struct ViewTable {
+    plan: LogicalPlan,
+    schema: SchemaRef,
+    config: ExecutionConfig,
+}
+
+impl ViewTable {
+    pub fn new(plan: LogicalPlan, config: ExecutionConfig) -> Self {
+        let schema = plan_schema(&plan);
+        Self {
+            plan,
+            schema,
+            config,
+        }
+    }
+}
+
+impl TableProvider for ViewTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn scan(
+        &self,
+        projection: &Option<Vec<usize>>,
+        _batch_size: usize,
+        _filters: &[Expr],
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        create_physical_plan(&self.plan, projection, &self.config)
+    }
+
+    fn statistics(&self) -> Statistics {
+        Statistics::default()
+    }
+}
@@ -58,6 +58,207 @@ class TestCase(unittest.TestCase):
         self.assertEqual(len(result.column(0)), 1)
         self.assertEqual(len(result.column(1)), 1)
 
+    def test_sort(self):
+        ctx = datafusion.ExecutionContext()
+
+        batch = pyarrow.RecordBatch.from_arrays(
+            [pyarrow.array([1, None, 3, 2]), pyarrow.array([1, 1, 2, 2])],
+            names=["a", "b"],
+        )
+        df = ctx.create_dataframe([[batch]])
+
+        result = pyarrow.Table.from_batches(
+            df.sort(f.col("a").sort(ascending=False, nulls_first=True)).collect()
+        )
+        self.assertEqual(result.column(0).to_pylist(), [None, 3, 2, 1])
+
+        result = pyarrow.Table.from_batches(
+            df.sort(f.col("a").sort(nulls_first=False)).collect()
+        )
+        self.assertEqual(result.column(0).to_pylist(), [1, 2, 3, None])
+
+        # multiple keys, with a plain expression sorted in ascending order
+        result = pyarrow.Table.from_batches(
+            df.sort(f.col("b").sort(ascending=False), f.col("a")).collect()
+        )
+        self.assertEqual(result.column(0).to_pylist(), [2, 3, None, 1])
+
+    def test_distinct(self):
+        ctx = datafusion.ExecutionContext()
+
+        batch = pyarrow.RecordBatch.from_arrays(
+            [pyarrow.array([1, 1, 2, 1]), pyarrow.array([4, 4, 5, 6]), pyarrow.array([1, 2, 3, 4])],
+            names=["a", "b", "c"],
+        )
+        df = ctx.create_dataframe([[batch]])
+
+        result = pyarrow.Table.from_batches(
+            df.select(f.col("a"), f.col("b")).distinct().sort(f.col("a"), f.col("b")).collect()
+        )
+        self.assertEqual(result.to_pydict(), {"a": [1, 1, 2], "b": [4, 6, 5]})
+
+        result = pyarrow.Table.from_batches(
+            df.drop_duplicates(subset=["a"]).collect()
+        )
+        self.assertEqual(result.to_pydict(), {"a": [1, 2], "b": [4, 5], "c": [1, 3]})
+
+        result = pyarrow.Table.from_batches(
+            df.drop_duplicates(subset=["a"], keep="last").collect()
+        )
+        self.assertEqual(result.to_pydict(), {"a": [2, 1], "b": [5, 6], "c": [3, 4]})
+
+        # the rows that are kept are chosen according to `order_by`
+        result = pyarrow.Table.from_batches(
+            df.drop_duplicates(subset=["a", "b"], order_by=[f.col("c").sort(ascending=False)]).collect()
+        )
+        self.assertEqual(result.to_pydict(), {"a": [1, 2, 1], "b": [6, 5, 4], "c": [4, 3, 2]})
+
+        with self.assertRaises(Exception):
+            df.drop_duplicates(subset=["a"], keep="any")
+
+    def test_drop_duplicates_values(self):
+        ctx = datafusion.ExecutionContext()
+
+        batch = pyarrow.RecordBatch.from_arrays(
+            [
+                pyarrow.array([0.0, -0.0, float("nan"), float("nan"), None, None]),
+                pyarrow.array([1, 2, 3, 4, 5, 6]),
+            ],
+            names=["a", "b"],
+        )
+        df = ctx.create_dataframe([[batch]])
+
+        # -0.0 and 0.0 are the same value, as are NaNs and nulls
+        result = df.drop_duplicates(subset=["a"]).select(f.col("b")).collect()
+        self.assertEqual(pyarrow.Table.from_batches(result).to_pydict(), {"b": [1, 3, 5]})
+
+        result = df.drop_duplicates(subset=["a"], keep="last").select(f.col("b")).collect()
+        self.assertEqual(pyarrow.Table.from_batches(result).to_pydict(), {"b": [2, 4, 6]})
+
+    def test_join_on_different_keys(self):
+        ctx = datafusion.ExecutionContext()
+
+        batch = pyarrow.RecordBatch.from_arrays(
+            [pyarrow.array([1, 2, 3]), pyarrow.array([4, 5, 6])],
+            names=["user_id", "value"],
+        )
+        df = ctx.create_dataframe([[batch]])
+
+        batch = pyarrow.RecordBatch.from_arrays(
+            [pyarrow.array([1, 2]), pyarrow.array([8, 10])],
+            names=["id", "value"],
+        )
+        df1 = ctx.create_dataframe([[batch]])
+
+        df = df.join(df1, left_on=["user_id"], right_on=["id"])
+        result = pyarrow.Table.from_batches(df.sort(f.col("user_id")).collect())
+
+        self.assertEqual(
+            sorted(result.column_names), ["id", "user_id", "value_l", "value_r"]
+        )
+        self.assertEqual(result.column("user_id").to_pylist(), [1, 2])
+        self.assertEqual(result.column("id").to_pylist(), [1, 2])
+        self.assertEqual(result.column("value_l").to_pylist(), [4, 5])
+        self.assertEqual(result.column("value_r").to_pylist(), [8, 10])
+
+    def test_join_suffixes(self):
+        ctx = datafusion.ExecutionContext()
+
+        batch = pyarrow.RecordBatch.from_arrays(
+            [pyarrow.array([1, 2]), pyarrow.array([4, 5])],
+            names=["a", "b"],
+        )
+        df = ctx.create_dataframe([[batch]])
+        df1 = ctx.create_dataframe([[batch]])
+
+        df = df.join(df1, on=["a"], suffixes=("_x", "_y"))
+        result = pyarrow.Table.from_batches(df.sort(f.col("a")).collect())
+
+        self.assertEqual(sorted(result.column_names), ["a", "b_x", "b_y"])
+        self.assertEqual(result.column("b_x").to_pylist(), [4, 5])
+        self.assertEqual(result.column("b_y").to_pylist(), [4, 5])
+
+        with self.assertRaises(Exception):
+            df.join(df1, left_on=["a"])
+
+    def test_join_types(self):
+        ctx = datafusion.ExecutionContext()
+
+        batch = pyarrow.RecordBatch.from_arrays(
+            [pyarrow.array([1, 2, None]), pyarrow.array([4, 5, 6])],
+            names=["a", "b"],
+        )
+        df = ctx.create_dataframe([[batch]])
+
+        batch = pyarrow.RecordBatch.from_arrays(
+            [pyarrow.array([1, 1, 3, None]), pyarrow.array([7, 8, 9, 10])],
+            names=["a", "c"],
+        )
+        df1 = ctx.create_dataframe([[batch]])
+
+        def collect(df, *sort):
+            return pyarrow.Table.from_batches(
+                df.sort(*[f.col(c).sort(nulls_first=False) for c in sort]).collect()
+            ).to_pydict()
+
+        # rows with a match are returned once, and null keys never match
+        self.assertEqual(
+            collect(df.join(df1, on=["a"], how="semi"), "b"), {"a": [1], "b": [4]}
+        )
+        self.assertEqual(
+            collect(df.join(df1, on=["a"], how="anti"), "b"), {"a": [2, None], "b": [5, 6]}
+        )
+
+        result = collect(df.join(df1, on=["a"], how="full"), "b", "c")
+        self.assertEqual(result["a"], [1, 1, 2, None, 3, None])
+        self.assertEqual(result["b"], [4, 4, 5, 6, None, None])
+        self.assertEqual(result["c"], [7, 8, None, None, 9, 10])
+
+        result = collect(
+            df.select(f.col("b")).join(df1.select(f.col("c")), how="cross"), "b", "c"
+        )
+        self.assertEqual(result["b"], [4] * 4 + [5] * 4 + [6] * 4)
+        self.assertEqual(result["c"], [7, 8, 9, 10] * 3)
+
+        with self.assertRaises(Exception):
+            df.join(df1, on=["a"], how="cross")
+
+    def test_union(self):
+        ctx = datafusion.ExecutionContext()
+
+        batch = pyarrow.RecordBatch.from_arrays(
+            [pyarrow.array([1, 2]), pyarrow.array(["x", "y"])],
+            names=["a", "b"],
+        )
+        df = ctx.create_dataframe([[batch]])
+
+        batch = pyarrow.RecordBatch.from_arrays(
+            [pyarrow.array(["y", "z"]), pyarrow.array([2, 3], pyarrow.int32())],
+            names=["b", "a"],
+        )
+        df1 = ctx.create_dataframe([[batch]])
+
+        result = pyarrow.Table.from_batches(
+            df.union(df).sort(f.col("a")).collect()
+        )
+        self.assertEqual(result.to_pydict(), {"a": [1, 1, 2, 2], "b": ["x", "x", "y", "y"]})
+
+        # columns are matched by name and `a` is widened to int64
+        result = pyarrow.Table.from_batches(
+            df.union(df1, by_name=True).sort(f.col("a")).collect()
+        )
+        self.assertEqual(result.schema.field("a").type, pyarrow.int64())
+        self.assertEqual(result.to_pydict(), {"a": [1, 2, 2, 3], "b": ["x", "y", "y", "z"]})
+
+        result = pyarrow.Table.from_batches(
+            df.union(df1, distinct=True, by_name=True).sort(f.col("a")).collect()
+        )
+        self.assertEqual(result.to_pydict(), {"a": [1, 2, 3], "b": ["x", "y", "z"]})
+
+        # by position, the types of the columns don't match
+        with self.assertRaises(Exception):
+            df.union(df1)
+
     def test_udf(self):
         df = self._prepare()
 
@@ -3,6 +3,11 @@ import tempfile
 import datetime
 import os.path
 import shutil
+import gzip
+import math
+import bz2
+import lzma
+import sqlite3
 
 import numpy
 import pyarrow
@@ -10,6 +15,21 @@ import datafusion
 
 # used to write parquet files
 import pyarrow.parquet
+import pyarrow.dataset
+import pyarrow.feather
+import pyarrow.fs
+
+# used to write avro files
+try:
+    import fastavro
+except ImportError:
+    fastavro = None
+
+# used to write zstd-compressed files
+try:
+    import zstandard
+except ImportError:
+    zstandard = None
 
 from tests.generic import *
 
@@ -29,6 +49,16 @@ class TestCase(unittest.TestCase):
         with self.assertRaises(Exception):
             datafusion.Context().sql("SELECT a FROM b").collect()
 
+    def test_config(self):
+        ctx = datafusion.ExecutionContext(batch_size=2, target_partitions=2)
+
+        path = write_parquet(os.path.join(self.test_dir, 'a.parquet'), data())
+        ctx.register_parquet("t", path)
+
+        result = ctx.sql("SELECT a FROM t").collect()
+        self.assertEqual(sum(len(batch) for batch in result), 100)
+        self.assertTrue(all(len(batch) <= 2 for batch in result))
+
     def test_register(self):
         ctx = datafusion.ExecutionContext()
 
@@ -38,6 +68,785 @@ class TestCase(unittest.TestCase):
 
         self.assertEqual(ctx.tables(), {"t"})
 
+    def test_sql_full_and_cross_joins(self):
+        ctx = datafusion.ExecutionContext()
+
+        batch = pyarrow.RecordBatch.from_arrays([pyarrow.array([1, 2])], names=["a"])
+        ctx.register_record_batches("t", [[batch]])
+        batch = pyarrow.RecordBatch.from_arrays([pyarrow.array([2, 3])], names=["b"])
+        ctx.register_record_batches("u", [[batch]])
+        batch = pyarrow.RecordBatch.from_arrays([pyarrow.array([2, 4])], names=["a"])
+        ctx.register_record_batches("v", [[batch]])
+
+        def rows(query):
+            result = pyarrow.Table.from_batches(ctx.sql(query).collect()).to_pydict()
+            return sorted(
+                zip(*result.values()), key=lambda row: [(x is None, x or 0) for x in row]
+            )
+
+        full = [(1, None), (2, 2), (None, 3)]
+        self.assertEqual(rows("SELECT a, b FROM t FULL JOIN u ON a = b"), full)
+        self.assertEqual(rows("SELECT a, b FROM t FULL OUTER JOIN u ON u.b = t.a"), full)
+        # keys of `USING` are returned once
+        self.assertEqual(rows("SELECT a FROM t FULL JOIN v USING (a)"), [(1,), (2,), (4,)])
+        self.assertEqual(
+            rows("SELECT a, b FROM t CROSS JOIN u"), [(1, 2), (1, 3), (2, 2), (2, 3)]
+        )
+        # implicit cross joins, with and without keys in `WHERE`
+        self.assertEqual(rows("SELECT a, b FROM t, u WHERE a < b"), [(1, 2), (1, 3), (2, 3)])
+        self.assertEqual(rows("SELECT a, b FROM t, u WHERE a = b"), [(2, 2)])
+        # joins inside subqueries and after other joins
+        self.assertEqual(
+            rows("SELECT COUNT(*) FROM (SELECT b FROM t JOIN u ON a = b CROSS JOIN v) w"),
+            [(2,)],
+        )
+
+        with self.assertRaisesRegex(Exception, "must compare columns"):
+            ctx.sql("SELECT a, b FROM t FULL JOIN u ON a < b")
+        with self.assertRaisesRegex(Exception, "Natural"):
+            ctx.sql("SELECT a, b FROM t NATURAL FULL JOIN u")
+        # the tables of the joins are only registered while the query is planned
+        self.assertEqual(ctx.tables(), {"t", "u", "v"})
+
+    def test_register_csv(self):
+        ctx = datafusion.ExecutionContext()
+
+        path = os.path.join(self.test_dir, 'a.csv')
+        with open(path, 'w') as f:
+            f.write('a,b\n1,x\n2,y\n3,z\n')
+
+        ctx.register_csv("t", path)
+        ctx.register_csv(
+            "t1", path,
+            schema=pyarrow.schema([("a", pyarrow.int32()), ("b", pyarrow.utf8())]),
+        )
+
+        self.assertEqual(ctx.tables(), {"t", "t1"})
+
+        result = ctx.sql("SELECT a, b FROM t").collect()[0]
+        self.assertEqual(result.column(0), pyarrow.array([1, 2, 3]))
+        self.assertEqual(result.column(1), pyarrow.array(["x", "y", "z"]))
+
+        result = ctx.sql("SELECT a FROM t1").collect()[0]
+        self.assertEqual(result.column(0), pyarrow.array([1, 2, 3], pyarrow.int32()))
+
+    def test_register_csv_options(self):
+        ctx = datafusion.ExecutionContext()
+
+        path = os.path.join(self.test_dir, 'a.csv')
+        with open(path, 'w') as f:
+            f.write('1|x\n2|y\n')
+
+        ctx.register_csv("t", path, has_header=False, delimiter="|")
+
+        result = ctx.sql("SELECT * FROM t").collect()[0]
+        self.assertEqual(result.column(0), pyarrow.array([1, 2]))
+
+        with self.assertRaises(Exception):
+            ctx.register_csv("t2", path, delimiter="||")
+
+    def test_register_json(self):
+        ctx = datafusion.ExecutionContext()
+
+        path = os.path.join(self.test_dir, 'a.json')
+        with open(path, 'w') as f:
+            f.write('{"a": 1, "b": "x"}\n{"a": 2, "b": "y"}\n{"a": 3}\n')
+
+        ctx.register_json("t", path)
+        ctx.register_json("t1", path, schema=pyarrow.schema([("a", pyarrow.float64())]))
+
+        self.assertEqual(ctx.tables(), {"t", "t1"})
+
+        result = ctx.sql("SELECT a, b FROM t").collect()[0]
+        self.assertEqual(result.column(0), pyarrow.array([1, 2, 3]))
+        self.assertEqual(result.column(1), pyarrow.array(["x", "y", None]))
+
+        result = ctx.sql("SELECT a FROM t1").collect()[0]
+        self.assertEqual(result.column(0), pyarrow.array([1.0, 2.0, 3.0]))
+
+    def test_register_record_batches(self):
+        ctx = datafusion.ExecutionContext()
+
+        batch = pyarrow.RecordBatch.from_arrays(
+            [pyarrow.array([1, 2, 3]), pyarrow.array([4, 5, 6])],
+            names=["a", "b"],
+        )
+        ctx.register_record_batches("t", [[batch]])
+
+        self.assertEqual(ctx.tables(), {"t"})
+
+        result = ctx.sql("SELECT a + b FROM t").collect()[0]
+        self.assertEqual(result.column(0), pyarrow.array([5, 7, 9]))
+
+        with self.assertRaises(Exception):
+            ctx.register_record_batches("t1", [[]])
+
+    def test_register_view(self):
+        ctx = datafusion.ExecutionContext()
+        f = datafusion.functions
+
+        batch = pyarrow.RecordBatch.from_arrays(
+            [pyarrow.array([1, 2, 3]), pyarrow.array([4, 5, 6])],
+            names=["a", "b"],
+        )
+        df = ctx.create_dataframe([[batch]])
+        df = df.select((f.col("a") + f.col("b")).alias("c"), f.col("a")).filter(f.col("a") > f.lit(1))
+
+        ctx.register_view("v", df)
+
+        result = ctx.sql("SELECT c FROM v").collect()[0]
+        self.assertEqual(result.column(0), pyarrow.array([7, 9]))
+
+    def test_deregister_table(self):
+        ctx = datafusion.ExecutionContext()
+
+        path = write_parquet(os.path.join(self.test_dir, 'a.parquet'), data())
+        ctx.register_parquet("t", path)
+        ctx.register_parquet("t1", path)
+
+        df = ctx.sql("SELECT a FROM t")
+        ctx.deregister_table("t")
+
+        self.assertEqual(ctx.tables(), {"t1"})
+        # existing DataFrames still reference the table
+        self.assertEqual(len(df.collect()[0].column(0)), 100)
+
+        with self.assertRaises(Exception):
+            ctx.sql("SELECT a FROM t")
+        with self.assertRaises(Exception):
+            ctx.deregister_table("t")
+
+    def test_create_dataframe_is_anonymous(self):
+        ctx = datafusion.ExecutionContext()
+
+        batch = pyarrow.RecordBatch.from_arrays([pyarrow.array([1, 2, 3])], names=["a"])
+        df = ctx.create_dataframe([[batch]])
+
+        self.assertEqual(ctx.tables(), set())
+        self.assertEqual(df.collect()[0].column(0), pyarrow.array([1, 2, 3]))
+
+    def test_register_table(self):
+        class Provider:
+            def __init__(self):
+                self.scans = []
+
+            def schema(self):
+                return pyarrow.schema([("a", pyarrow.int64()), ("b", pyarrow.int64())])
+
+            def scan(self, projection, filters, limit):
+                self.scans.append((projection, filters, limit))
+                arrays = {"a": pyarrow.array([1, 2, 3]), "b": pyarrow.array([4, 5, 6])}
+                names = projection or ["a", "b"]
+                return [pyarrow.RecordBatch.from_arrays([arrays[n] for n in names], names=names)]
+
+        ctx = datafusion.ExecutionContext()
+        provider = Provider()
+        ctx.register_table("t", provider)
+
+        self.assertEqual(ctx.tables(), {"t"})
+
+        result = ctx.sql("SELECT a FROM t WHERE a > 1").collect()[0]
+        self.assertEqual(result.column(0), pyarrow.array([2, 3]))
+
+        projection, filters, limit = provider.scans[-1]
+        self.assertEqual(projection, ["a"])
+        self.assertEqual(len(filters), 1)
+        self.assertEqual(filters[0].variant, "BinaryExpr")
+        self.assertEqual(filters[0].operator, ">")
+        self.assertEqual(filters[0].operands[0].name, "a")
+        self.assertEqual(filters[0].operands[1].value, 1)
+        self.assertIsNone(limit)
+
+    def test_register_table_metadata(self):
+        schema = pyarrow.schema(
+            [pyarrow.field("a", pyarrow.int64(), metadata={"unit": "m"})],
+            metadata={"origin": "sensor"},
+        )
+
+        class Provider:
+            def schema(self):
+                return schema
+
+            def scan(self, projection, filters, limit):
+                return [pyarrow.RecordBatch.from_arrays([pyarrow.array([1, 2])], schema=schema)]
+
+        ctx = datafusion.ExecutionContext()
+        ctx.register_table("t", Provider())
+
+        result = ctx.table_schema("t")
+        self.assertEqual(result.metadata, {b"origin": b"sensor"})
+        self.assertEqual(result.field("a").metadata, {b"unit": b"m"})
+
+    def test_register_table_supports_filter(self):
+        class Provider:
+            def __init__(self):
+                self.scans = []
+
+            def schema(self):
+                return pyarrow.schema([("a", pyarrow.int64()), ("b", pyarrow.int64())])
+
+            def supports_filter(self, filter):
+                # declares the filters on `a` as fully applied, although `scan` ignores every filter
+                return filter.operands[0].name == "a"
+
+            def scan(self, projection, filters, limit):
+                self.scans.append(filters)
+                arrays = {"a": pyarrow.array([1, 2, 3]), "b": pyarrow.array([4, 5, 6])}
+                names = projection or ["a", "b"]
+                return [pyarrow.RecordBatch.from_arrays([arrays[n] for n in names], names=names)]
+
+        ctx = datafusion.ExecutionContext()
+        provider = Provider()
+        ctx.register_table("t", provider)
+
+        result = ctx.sql("SELECT a FROM t WHERE a > 1 AND b < 6").collect()[0]
+
+        # both filters are passed to `scan`...
+        filters = provider.scans[-1]
+        self.assertEqual(sorted(f.operands[0].name for f in filters), ["a", "b"])
+        # ...but only the one that is not fully applied is re-applied to its result
+        self.assertEqual(result.column(0), pyarrow.array([1, 2]))
+
+    def test_register_table_scan_schema(self):
+        class Provider:
+            def __init__(self, batch):
+                self.batch = batch
+
+            def schema(self):
+                return pyarrow.schema([("a", pyarrow.int64()), ("b", pyarrow.int64())])
+
+            def scan(self, projection, filters, limit):
+                # ignores the projection
+                return [self.batch]
+
+        ctx = datafusion.ExecutionContext()
+
+        # the columns are selected by name
+        batch = pyarrow.RecordBatch.from_arrays(
+            [pyarrow.array([4, 5]), pyarrow.array([1, 2])], names=["b", "a"]
+        )
+        ctx.register_table("t", Provider(batch))
+        result = ctx.sql("SELECT a FROM t").collect()[0]
+        self.assertEqual(result.column(0), pyarrow.array([1, 2]))
+
+        batch = pyarrow.RecordBatch.from_arrays([pyarrow.array([1, 2])], names=["a"])
+        ctx.register_table("u", Provider(batch))
+        with self.assertRaisesRegex(Exception, "must have the column 'b'"):
+            ctx.sql("SELECT b FROM u").collect()
+
+        batch = pyarrow.RecordBatch.from_arrays(
+            [pyarrow.array(["x", "y"]), pyarrow.array([4, 5])], names=["a", "b"]
+        )
+        ctx.register_table("v", Provider(batch))
+        with self.assertRaisesRegex(Exception, "is of type Utf8"):
+            ctx.sql("SELECT a FROM v").collect()
+
+    def test_register_dataset(self):
+        ctx = datafusion.ExecutionContext()
+
+        write_parquet(os.path.join(self.test_dir, 'a.parquet'), pyarrow.array([1, 2, 3]))
+        write_parquet(os.path.join(self.test_dir, 'b.parquet'), pyarrow.array([4, 5, 6]))
+        dataset = pyarrow.dataset.dataset(self.test_dir, format="parquet")
+
+        ctx.register_dataset("t", dataset)
+
+        self.assertEqual(ctx.tables(), {"t"})
+
+        result = ctx.sql("SELECT a FROM t WHERE a > 2").collect()
+        result = sorted(sum((batch.column(0).to_pylist() for batch in result), []))
+        self.assertEqual(result, [3, 4, 5, 6])
+
+    def test_schemas(self):
+        ctx = datafusion.ExecutionContext()
+
+        staging = write_parquet(os.path.join(self.test_dir, 'a.parquet'), pyarrow.array([1, 2]))
+        production = write_parquet(os.path.join(self.test_dir, 'b.parquet'), pyarrow.array([1, 2, 3]))
+
+        # a dotted name is a plain table name unless its schema was created
+        ctx.register_parquet("legacy.t", staging)
+        self.assertEqual(ctx.tables(), {"legacy.t"})
+        ctx.deregister_table("legacy.t")
+
+        with self.assertRaisesRegex(Exception, "reserved"):
+            ctx.register_parquet("information_schema.t", staging)
+
+        ctx.create_schema("staging")
+        ctx.create_schema("production")
+        with self.assertRaises(Exception):
+            ctx.create_schema("staging")
+
+        ctx.register_parquet("staging.t", staging)
+        ctx.register_parquet("production.t", production)
+
+        self.assertEqual(ctx.schemas(), {"staging", "production"})
+        self.assertEqual(ctx.tables(), {"staging.t", "production.t"})
+        self.assertEqual(ctx.tables(schema="staging"), {"t"})
+
+        result = ctx.sql("SELECT COUNT(a) FROM production.t").collect()[0]
+        self.assertEqual(result.column(0), pyarrow.array([3], pyarrow.uint64()))
+
+    def test_information_schema(self):
+        ctx = datafusion.ExecutionContext()
+
+        path = write_parquet(os.path.join(self.test_dir, 'a.parquet'), data())
+        ctx.register_parquet("t", path)
+        ctx.create_schema("staging")
+        ctx.register_parquet("staging.t", path)
+
+        result = ctx.sql("SHOW TABLES").collect()[0].to_pydict()
+        self.assertEqual(result["table_schema"][:2], ["public", "staging"])
+        self.assertEqual(result["table_name"][:2], ["t", "t"])
+        self.assertEqual(result["table_type"][:2], ["BASE TABLE", "BASE TABLE"])
+
+        expected = {"column_name": ["a"], "data_type": ["DOUBLE"], "is_nullable": ["YES"]}
+        self.assertEqual(ctx.sql("SHOW COLUMNS FROM t").collect()[0].to_pydict(), expected)
+        self.assertEqual(ctx.sql("DESCRIBE staging.t").collect()[0].to_pydict(), expected)
+
+        result = ctx.sql(
+            "SELECT column_name FROM information_schema.columns WHERE table_schema = 'staging'"
+        ).collect()[0]
+        self.assertEqual(result.column(0), pyarrow.array(["a"]))
+
+        # the views are not listed among the tables
+        self.assertEqual(ctx.tables(), {"t", "staging.t"})
+
+        with self.assertRaisesRegex(Exception, "can't be deregistered"):
+            ctx.deregister_table("information_schema.tables")
+        ctx.sql("SHOW TABLES").collect()
+
+        with self.assertRaisesRegex(Exception, "No table named 'u'"):
+            ctx.sql("DESCRIBE u")
+
+        # the views describe the tables registered when they are read, not when they are planned
+        df = ctx.table("information_schema.tables")
+        ctx.register_parquet("u", path)
+        result = df.collect()[0].to_pydict()
+        self.assertIn("u", result["table_name"])
+        self.assertEqual(ctx.sql("DESCRIBE u").collect()[0].to_pydict(), expected)
+
+    def test_table(self):
+        ctx = datafusion.ExecutionContext()
+        f = datafusion.functions
+
+        path = write_parquet(os.path.join(self.test_dir, 'a.parquet'), pyarrow.array([1, 2, 3]))
+        ctx.register_parquet("t", path)
+
+        result = ctx.table("t").filter(f.col("a") > f.lit(1)).collect()[0]
+        self.assertEqual(result.column(0), pyarrow.array([2, 3]))
+
+        with self.assertRaisesRegex(Exception, "No table named 'b'"):
+            ctx.table("b")
+
+    def test_read(self):
+        ctx = datafusion.ExecutionContext()
+
+        parquet = write_parquet(os.path.join(self.test_dir, 'a.parquet'), pyarrow.array([1, 2, 3]))
+        csv = os.path.join(self.test_dir, 'a.csv')
+        with open(csv, 'w') as f:
+            f.write('a\n1\n2\n3\n')
+        json = os.path.join(self.test_dir, 'a.json')
+        with open(json, 'w') as f:
+            f.write('{"a": 1}\n{"a": 2}\n{"a": 3}\n')
+
+        for df in [ctx.read_parquet(parquet), ctx.read_csv(csv), ctx.read_json(json)]:
+            self.assertEqual(df.collect()[0].column(0), pyarrow.array([1, 2, 3]))
+
+        result = ctx.read_csv(csv, schema=pyarrow.schema([("a", pyarrow.float64())])).collect()[0]
+        self.assertEqual(result.column(0), pyarrow.array([1.0, 2.0, 3.0]))
+
+        self.assertEqual(ctx.tables(), set())
+
+    def test_schema(self):
+        ctx = datafusion.ExecutionContext()
+        f = datafusion.functions
+
+        batch = pyarrow.RecordBatch.from_arrays(
+            [pyarrow.array([1, 2, 3]), pyarrow.array(["a", "b", None])],
+            names=["a", "b"],
+        )
+        ctx.register_record_batches("t", [[batch]])
+
+        self.assertEqual(ctx.table_schema("t"), batch.schema)
+        with self.assertRaises(Exception):
+            ctx.table_schema("b")
+
+        df = ctx.table("t").select(f.col("a"), (f.col("a") + f.col("a")).alias("c"))
+        self.assertEqual(
+            df.schema(),
+            pyarrow.schema([("a", pyarrow.int64()), ("c", pyarrow.int64())]),
+        )
+
+    def test_register_ipc(self):
+        ctx = datafusion.ExecutionContext()
+
+        table = pyarrow.Table.from_arrays([pyarrow.array([1, 2, 3]), pyarrow.array(["x", "y", "z"])], names=["a", "b"])
+
+        file_path = os.path.join(self.test_dir, 'a.arrow')
+        pyarrow.feather.write_feather(table, file_path, compression="uncompressed")
+
+        stream_path = os.path.join(self.test_dir, 'a.arrows')
+        with pyarrow.OSFile(stream_path, 'wb') as sink:
+            with pyarrow.ipc.new_stream(sink, table.schema) as writer:
+                writer.write_table(table)
+
+        ctx.register_ipc("file", file_path)
+        ctx.register_ipc("stream", stream_path)
+
+        for name in ["file", "stream"]:
+            result = ctx.sql(f"SELECT b FROM {name} WHERE a > 1").collect()[0]
+            self.assertEqual(result.column(0), pyarrow.array(["y", "z"]))
+
+    @unittest.skipIf(fastavro is None, "fastavro is required to write Avro files")
+    def test_register_avro(self):
+        schema = {
+            "type": "record",
+            "name": "r",
+            "fields": [
+                {"name": "a", "type": "long"},
+                {"name": "b", "type": ["null", "string"]},
+                {"name": "c", "type": {"type": "array", "items": "int"}},
+                {"name": "d", "type": {"type": "record", "name": "d", "fields": [{"name": "e", "type": "double"}]}},
+                {"name": "f", "type": ["null", "double"]},
+            ],
+        }
+        records = [
+            {"a": 1, "b": "x", "c": [1, 2], "d": {"e": 1.0}, "f": float("nan")},
+            {"a": 2, "b": None, "c": [], "d": {"e": 2.0}, "f": float("inf")},
+        ]
+        path = os.path.join(self.test_dir, 'a.avro')
+        with open(path, 'wb') as f:
+            fastavro.writer(f, fastavro.parse_schema(schema), records)
+
+        ctx = datafusion.ExecutionContext()
+        ctx.register_avro("t", path)
+
+        self.assertEqual(ctx.table_schema("t"), pyarrow.schema([
+            pyarrow.field("a", pyarrow.int64(), False),
+            pyarrow.field("b", pyarrow.utf8(), True),
+            pyarrow.field("c", pyarrow.list_(pyarrow.field("item", pyarrow.int32(), False)), False),
+            pyarrow.field("d", pyarrow.struct([pyarrow.field("e", pyarrow.float64(), False)]), False),
+            pyarrow.field("f", pyarrow.float64(), True),
+        ]))
+
+        result = ctx.sql("SELECT a, b, c, d, f FROM t").collect()[0]
+        self.assertEqual(result.column(0), pyarrow.array([1, 2]))
+        self.assertEqual(result.column(1), pyarrow.array(["x", None]))
+        self.assertEqual(result.column(2).to_pylist(), [[1, 2], []])
+        self.assertEqual(result.column(3).to_pylist(), [{"e": 1.0}, {"e": 2.0}])
+
+        # NaN and infinity are read as such, not as nulls
+        f = result.column(4).to_pylist()
+        self.assertTrue(math.isnan(f[0]))
+        self.assertEqual(f[1], float("inf"))
+
+    def test_register_listing_table(self):
+        ctx = datafusion.ExecutionContext()
+
+        for year, month, values in [(2025, 12, [1, 2]), (2026, 1, [3]), (2026, 2, [4, 5])]:
+            directory = os.path.join(self.test_dir, f'year={year}', f'month={month}')
+            os.makedirs(directory)
+            write_parquet(os.path.join(directory, 'part-0.parquet'), pyarrow.array(values))
+
+        ctx.register_listing_table("t", self.test_dir)
+
+        self.assertEqual(ctx.table_schema("t").names, ["a", "year", "month"])
+        self.assertEqual(ctx.table_schema("t").field("year").type, pyarrow.int64())
+
+        result = ctx.sql("SELECT a, month FROM t WHERE year = 2026 AND month > 1").collect()
+        self.assertEqual(len(result), 1)
+        self.assertEqual(result[0].column(0), pyarrow.array([4, 5]))
+        self.assertEqual(result[0].column(1), pyarrow.array([2, 2]))
+
+        ctx.register_listing_table("t1", self.test_dir, partitioning=[("year", pyarrow.utf8())])
+        result = ctx.sql("SELECT COUNT(a) FROM t1 WHERE year = '2025'").collect()[0]
+        self.assertEqual(result.column(0), pyarrow.array([2], pyarrow.uint64()))
+
+    def test_register_listing_table_schemas(self):
+        ctx = datafusion.ExecutionContext()
+
+        # the keys are not in the same order in every directory, and one of the files has another column
+        directory = os.path.join(self.test_dir, 'year=2026', 'kind=x')
+        os.makedirs(directory)
+        write_parquet(os.path.join(directory, 'part-0.parquet'), pyarrow.array([1, 2]))
+        directory = os.path.join(self.test_dir, 'kind=y', 'year=__HIVE_DEFAULT_PARTITION__')
+        os.makedirs(directory)
+        pyarrow.parquet.write_table(
+            pyarrow.Table.from_arrays([pyarrow.array([3]), pyarrow.array(["z"])], names=['a', 'b']),
+            os.path.join(directory, 'part-0.parquet'),
+        )
+
+        ctx.register_listing_table("t", self.test_dir)
+
+        schema = ctx.table_schema("t")
+        self.assertEqual(schema.names, ["a", "b", "kind", "year"])
+        self.assertEqual(schema.field("year").type, pyarrow.int64())
+        self.assertTrue(schema.field("year").nullable)
+
+        result = ctx.sql("SELECT a, b, kind, year FROM t ORDER BY a").collect()
+        result = pyarrow.Table.from_batches(result).to_pydict()
+        self.assertEqual(
+            result,
+            {"a": [1, 2, 3], "b": [None, None, "z"], "kind": ["x", "x", "y"], "year": [2026, 2026, None]},
+        )
+
+        result = ctx.sql("SELECT COUNT(*) FROM t WHERE year IS NULL").collect()[0]
+        self.assertEqual(result.column(0), pyarrow.array([1], pyarrow.uint64()))
+
+    def test_register_multiple_files(self):
+        ctx = datafusion.ExecutionContext()
+
+        a = write_parquet(os.path.join(self.test_dir, 'a.parquet'), pyarrow.array([1, 2]))
+        b = write_parquet(os.path.join(self.test_dir, 'b.parquet'), pyarrow.array([3]))
+        pyarrow.parquet.write_table(
+            pyarrow.Table.from_arrays([pyarrow.array([4]), pyarrow.array(["x"])], names=['a', 'b']),
+            os.path.join(self.test_dir, 'c.parquet'),
+        )
+
+        ctx.register_parquet("glob", os.path.join(self.test_dir, '*.parquet'))
+        ctx.register_parquet("list", [a, b])
+
+        self.assertEqual(ctx.table_schema("glob").names, ["a", "b"])
+        result = pyarrow.Table.from_batches(ctx.sql("SELECT a, b FROM glob").collect()).to_pydict()
+        result = sorted(zip(result["a"], result["b"]))
+        self.assertEqual(result, [(1, None), (2, None), (3, None), (4, "x")])
+
+        result = ctx.sql("SELECT COUNT(a) FROM list").collect()[0]
+        self.assertEqual(result.column(0), pyarrow.array([3], pyarrow.uint64()))
+
+        # directories of a list are replaced by their Parquet files
+        directory = os.path.join(self.test_dir, 'data')
+        os.makedirs(directory)
+        write_parquet(os.path.join(directory, 'd.parquet'), pyarrow.array([5, 6]))
+        ctx.register_parquet("directories", [a, directory])
+        result = ctx.sql("SELECT SUM(a) FROM directories").collect()[0]
+        self.assertEqual(result.column(0), pyarrow.array([14]))
+
+        with self.assertRaises(Exception):
+            ctx.register_parquet("none", os.path.join(self.test_dir, '*.csv'))
+
+    def test_register_multiple_files_widened(self):
+        ctx = datafusion.ExecutionContext()
+
+        write_parquet(os.path.join(self.test_dir, 'a.parquet'), pyarrow.array([1, 2], pyarrow.int32()))
+        pyarrow.parquet.write_table(
+            pyarrow.Table.from_arrays([pyarrow.array([3], pyarrow.int64()), pyarrow.array(["x"])], names=['a', 'b']),
+            os.path.join(self.test_dir, 'b.parquet'),
+        )
+        ctx.register_parquet("t", os.path.join(self.test_dir, '*.parquet'))
+
+        self.assertEqual(ctx.table_schema("t").field("a").type, pyarrow.int64())
+        result = pyarrow.Table.from_batches(ctx.sql("SELECT a FROM t").collect())
+        self.assertEqual(sorted(result.column(0).to_pylist()), [1, 2, 3])
+        # the files without the projected column still contribute their rows
+        result = pyarrow.Table.from_batches(ctx.sql("SELECT b FROM t").collect())
+        self.assertEqual(result.num_rows, 3)
+        self.assertEqual(result.column(0).null_count, 2)
+
+        for i, data in enumerate(['c\n1\n', 'c\n1.5\n']):
+            with open(os.path.join(self.test_dir, f'{i}.csv'), 'w') as f:
+                f.write(data)
+        ctx.register_csv("u", os.path.join(self.test_dir, '*.csv'))
+
+        self.assertEqual(ctx.table_schema("u").field("c").type, pyarrow.float64())
+        result = pyarrow.Table.from_batches(ctx.sql("SELECT c FROM u").collect())
+        self.assertEqual(sorted(result.column(0).to_pylist()), [1.0, 1.5])
+
+    def test_register_multiple_files_incompatible(self):
+        ctx = datafusion.ExecutionContext()
+
+        for i, data in enumerate(['a\n1\n', 'a\nx\n']):
+            with open(os.path.join(self.test_dir, f'{i}.csv'), 'w') as f:
+                f.write(data)
+
+        with self.assertRaisesRegex(Exception, "incompatible"):
+            ctx.register_csv("t", os.path.join(self.test_dir, '*.csv'))
+
+    def test_register_compressed(self):
+        ctx = datafusion.ExecutionContext()
+
+        csv = b'a,b\n1,x\n2,y\n'
+        json = b'{"a": 1, "b": "x"}\n{"a": 2, "b": "y"}\n'
+        for name, module in [('gz', gzip), ('bz2', bz2), ('xz', lzma)]:
+            with module.open(os.path.join(self.test_dir, f'a.csv.{name}'), 'wb') as f:
+                f.write(csv)
+            with module.open(os.path.join(self.test_dir, f'a.json.{name}'), 'wb') as f:
+                f.write(json)
+
+            ctx.register_csv(f"csv_{name}", os.path.join(self.test_dir, f'a.csv.{name}'))
+            ctx.register_json(f"json_{name}", os.path.join(self.test_dir, f'a.json.{name}'))
+
+        # explicit compression, regardless of the extension
+        with gzip.open(os.path.join(self.test_dir, 'b.csv'), 'wb') as f:
+            f.write(csv)
+        ctx.register_csv("csv_explicit", os.path.join(self.test_dir, 'b.csv'), compression="gzip")
+
+        for table in ctx.tables():
+            result = ctx.sql(f"SELECT a, b FROM {table}").collect()[0]
+            self.assertEqual(result.column(0), pyarrow.array([1, 2]))
+            self.assertEqual(result.column(1), pyarrow.array(["x", "y"]))
+
+        with self.assertRaises(Exception):
+            ctx.register_csv("t", os.path.join(self.test_dir, 'b.csv'), compression="lz4")
+
+    @unittest.skipIf(zstandard is None, "zstandard is required to write zstd files")
+    def test_register_zstd(self):
+        ctx = datafusion.ExecutionContext()
+
+        path = os.path.join(self.test_dir, 'a.json.zst')
+        with open(path, 'wb') as f:
+            f.write(zstandard.ZstdCompressor().compress(b'{"a": 1}\n{"a": 2}\n'))
+        ctx.register_json("t", path)
+
+        result = ctx.sql("SELECT a FROM t").collect()[0]
+        self.assertEqual(result.column(0), pyarrow.array([1, 2]))
+
+    def test_register_compressed_directory(self):
+        ctx = datafusion.ExecutionContext()
+
+        directory = os.path.join(self.test_dir, 'data')
+        os.makedirs(directory)
+        for name in ['a.txt', 'b.txt']:
+            with gzip.open(os.path.join(directory, name), 'wb') as f:
+                f.write(b'a\n1\n')
+        # files without the extension are not read
+        with open(os.path.join(directory, 'c.csv'), 'w') as f:
+            f.write('a\n2\n')
+
+        ctx.register_csv("t", directory, file_extension=".txt", compression="gzip")
+        result = ctx.sql("SELECT SUM(a) FROM t").collect()[0]
+        self.assertEqual(result.column(0), pyarrow.array([2]))
+
+        # a single uncompressed file is read by the engine, with the same `file_extension`
+        ctx.register_csv("u", directory, file_extension=".csv")
+        result = ctx.sql("SELECT SUM(a) FROM u").collect()[0]
+        self.assertEqual(result.column(0), pyarrow.array([2]))
+
+        with self.assertRaisesRegex(Exception, "No files"):
+            ctx.register_csv("v", directory, file_extension=".tsv")
+
+    def test_register_object_store(self):
+        ctx = datafusion.ExecutionContext()
+
+        store = datafusion.InMemoryObjectStore()
+        for i, values in enumerate([[1, 2], [3]]):
+            sink = pyarrow.BufferOutputStream()
+            pyarrow.parquet.write_table(pyarrow.table({"a": values}), sink)
+            store.put(f"bucket/data/part-{i}.parquet", sink.getvalue().to_pybytes())
+        store.put("bucket/a.csv", b'a,b\n1,x\n')
+        ctx.register_object_store("memory", store)
+
+        ctx.register_parquet("t", "memory://bucket/data")
+        ctx.register_csv("c", "memory://bucket/a.csv")
+
+        result = ctx.sql("SELECT SUM(a) FROM t").collect()[0]
+        self.assertEqual(result.column(0), pyarrow.array([6]))
+        result = ctx.sql("SELECT a, b FROM c").collect()[0]
+        self.assertEqual(result.column(1), pyarrow.array(["x"]))
+
+        # any pyarrow filesystem, e.g. the local one
+        path = write_parquet(os.path.join(self.test_dir, 'a.parquet'), pyarrow.array([1, 2]))
+        ctx.register_object_store("file", pyarrow.fs.LocalFileSystem())
+        ctx.register_parquet("local", f"file://{path}")
+        result = ctx.sql("SELECT SUM(a) FROM local").collect()[0]
+        self.assertEqual(result.column(0), pyarrow.array([3]))
+
+        with self.assertRaisesRegex(Exception, "same scheme"):
+            ctx.register_parquet("mixed", ["memory://bucket/data", f"file://{path}"])
+        with self.assertRaisesRegex(Exception, "same scheme"):
+            ctx.register_parquet("mixed", ["memory://bucket/data", path])
+        ctx.register_parquet("list", ["memory://bucket/data/part-0.parquet", "memory://bucket/data/part-1.parquet"])
+        result = ctx.sql("SELECT SUM(a) FROM list").collect()[0]
+        self.assertEqual(result.column(0), pyarrow.array([6]))
+
+        with self.assertRaises(Exception):
+            ctx.register_parquet("u", "s3://bucket/data")
+
+    def test_register_local_file_system(self):
+        ctx = datafusion.ExecutionContext()
+        ctx.register_object_store("local", datafusion.LocalFileSystem())
+
+        directory = os.path.join(self.test_dir, 'data')
+        os.makedirs(os.path.join(directory, 'nested'))
+        write_parquet(os.path.join(directory, 'part-0.parquet'), pyarrow.array([1, 2]))
+        write_parquet(os.path.join(directory, 'nested', 'part-1.parquet'), pyarrow.array([3]))
+        # hidden files are not listed
+        write_parquet(os.path.join(directory, '.part-2.parquet'), pyarrow.array([4]))
+        with open(os.path.join(self.test_dir, 'a.csv'), 'w') as f:
+            f.write('a,b\n1,x\n')
+
+        ctx.register_parquet("t", f"local://{directory}")
+        ctx.register_csv("c", f"local://{os.path.join(self.test_dir, 'a.csv')}")
+
+        result = ctx.sql("SELECT SUM(a) FROM t").collect()[0]
+        self.assertEqual(result.column(0), pyarrow.array([6]))
+        result = ctx.sql("SELECT a, b FROM c").collect()[0]
+        self.assertEqual(result.column(1), pyarrow.array(["x"]))
+
+        with self.assertRaises(Exception):
+            ctx.register_parquet("u", f"local://{os.path.join(self.test_dir, 'missing')}")
+
+    def test_register_sqlite(self):
+        path = os.path.join(self.test_dir, 'a.db')
+        connection = sqlite3.connect(path)
+        connection.execute("CREATE TABLE t (a INTEGER NOT NULL, b TEXT, c REAL, d BOOLEAN)")
+        connection.executemany(
+            "INSERT INTO t VALUES (?, ?, ?, ?)",
+            [(1, "x", 1.5, True), (2, None, 2.5, False), (3, "z", None, None)],
+        )
+        connection.execute("CREATE TABLE u (a INTEGER)")
+        connection.commit()
+        connection.close()
+
+        ctx = datafusion.ExecutionContext()
+        ctx.register_sqlite(path)
+        self.assertEqual(ctx.tables(), {"t", "u"})
+        self.assertEqual(ctx.table_schema("t"), pyarrow.schema([
+            pyarrow.field("a", pyarrow.int64(), False),
+            pyarrow.field("b", pyarrow.utf8(), True),
+            pyarrow.field("c", pyarrow.float64(), True),
+            pyarrow.field("d", pyarrow.bool_(), True),
+        ]))
+
+        result = ctx.sql("SELECT a, b FROM t WHERE a > 1 AND b IS NOT NULL").collect()[0]
+        self.assertEqual(result.column(0), pyarrow.array([3]))
+        self.assertEqual(result.column(1), pyarrow.array(["z"]))
+
+        result = ctx.sql("SELECT c, d FROM t WHERE a <> 3").collect()[0]
+        self.assertEqual(result.column(0), pyarrow.array([1.5, 2.5]))
+        self.assertEqual(result.column(1), pyarrow.array([True, False]))
+
+        ctx = datafusion.ExecutionContext(batch_size=2)
+        ctx.register_sqlite(path, tables=["t"])
+        self.assertEqual(ctx.tables(), {"t"})
+        batches = ctx.sql("SELECT a FROM t").collect()
+        self.assertEqual([batch.num_rows for batch in batches], [2, 1])
+
+    def test_register_sqlite_mixed_types(self):
+        path = os.path.join(self.test_dir, 'a.db')
+        connection = sqlite3.connect(path)
+        # SQLite does not enforce types: `a` has no declared type and `b` is a boolean stored as an integer
+        connection.execute("CREATE TABLE t (a, b BOOLEAN, c TEXT COLLATE NOCASE)")
+        connection.executemany(
+            "INSERT INTO t VALUES (?, ?, ?)",
+            [(10, 1, "a"), ("10", 2, "B"), (2, 0, "c")],
+        )
+        connection.commit()
+        connection.close()
+
+        ctx = datafusion.ExecutionContext()
+        ctx.register_sqlite(path)
+
+        # filters on columns whose values SQLite compares differently are applied by the engine
+        result = ctx.sql("SELECT a FROM t WHERE a = '10'").collect()
+        self.assertEqual(pyarrow.Table.from_batches(result).column(0).to_pylist(), ["10", "10"])
+
+        result = ctx.sql("SELECT b FROM t WHERE b = true").collect()
+        self.assertEqual(pyarrow.Table.from_batches(result).column(0).to_pylist(), [True, True])
+
+        # text is compared byte by byte, regardless of the collation of the column
+        result = ctx.sql("SELECT c FROM t WHERE c < 'b'").collect()
+        self.assertEqual(pyarrow.Table.from_batches(result).column(0).to_pylist(), ["a", "B"])
+
     def test_execute(self):
         data = [
             1, 1, 2, 2, 3, 11, 12